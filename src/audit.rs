@@ -0,0 +1,258 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use serenity::{
+    all::{AuditLogEntry, GuildId},
+    client::Context,
+    model::guild::audit_log::Action,
+};
+use tokio::time::sleep;
+use tracing::{debug, instrument};
+
+use crate::data::Data;
+
+const AUDIT_LOG_DELAY_IN_MILLISECONDS: u64 = 1500;
+const AUDIT_LOG_MAX_AGE_IN_SECONDS: i64 = 30;
+const AUDIT_LOG_LIMIT: u8 = 10;
+
+#[derive(Default)]
+pub struct AuditCounts {
+    counts: Mutex<HashMap<(u64, u8), HashMap<u64, u64>>>,
+}
+
+impl AuditCounts {
+    #[instrument(skip(self, entries))]
+    fn incremented(
+        &self,
+        guild_id: GuildId,
+        action: Action,
+        entries: Vec<AuditLogEntry>,
+    ) -> Vec<AuditLogEntry> {
+        let mut counts = self
+            .counts
+            .lock()
+            .expect("Audit count lock should never be poisoned.");
+
+        let seen = counts.entry((guild_id.get(), action.num())).or_default();
+        let mut current = HashMap::new();
+        let mut incremented = Vec::new();
+
+        for entry in entries {
+            let count = entry
+                .options
+                .as_ref()
+                .and_then(|options| options.count)
+                .unwrap_or(1);
+
+            // Unknown old entries are only recorded, their previous count can't be known
+            let previous = match seen.get(&entry.id.get()) {
+                Some(previous) => Some(*previous),
+                None if is_recent(&entry) => Some(0),
+                None => None,
+            };
+
+            current.insert(entry.id.get(), count);
+
+            if previous.is_some_and(|previous| count == previous + 1) {
+                incremented.push(entry);
+            }
+        }
+
+        *seen = current;
+
+        incremented
+    }
+}
+
+#[instrument(skip(ctx))]
+async fn can_view(ctx: &Context, guild_id: GuildId) -> bool {
+    let user_id = ctx.cache.current_user().id;
+
+    let member = match guild_id.member(ctx, user_id).await {
+        Ok(member) => member,
+        Err(err) => {
+            debug!(
+                "Failed to get own member in guild '{}': {err}",
+                guild_id.get()
+            );
+            return false;
+        }
+    };
+
+    // Viewing the audit log is a guild-wide permission, so channel overwrites don't apply.
+    #[allow(deprecated)]
+    guild_id
+        .to_guild_cached(ctx)
+        .is_some_and(|guild| guild.member_permissions(&member).view_audit_log())
+}
+
+#[instrument(skip(ctx))]
+async fn fetch(ctx: &Context, guild_id: GuildId, action: Option<Action>) -> Vec<AuditLogEntry> {
+    if !can_view(ctx, guild_id).await {
+        debug!(
+            "Missing permission to view audit logs of guild '{}'.",
+            guild_id.get()
+        );
+        return Vec::new();
+    }
+
+    // Audit log entries may be created after the gateway event is dispatched.
+    sleep(Duration::from_millis(AUDIT_LOG_DELAY_IN_MILLISECONDS)).await;
+
+    match guild_id
+        .audit_logs(&ctx.http, action, None, None, Some(AUDIT_LOG_LIMIT))
+        .await
+    {
+        Ok(logs) => logs.entries,
+        Err(err) => {
+            debug!(
+                "Failed to get audit logs of guild '{}': {err}",
+                guild_id.get()
+            );
+            Vec::new()
+        }
+    }
+}
+
+fn is_recent(entry: &AuditLogEntry) -> bool {
+    let oldest = chrono::Utc::now().timestamp() - AUDIT_LOG_MAX_AGE_IN_SECONDS;
+
+    entry.id.created_at().unix_timestamp() >= oldest
+}
+
+#[instrument(skip(ctx, filter))]
+pub async fn find(
+    ctx: &Context,
+    guild_id: GuildId,
+    action: Action,
+    filter: impl Fn(&AuditLogEntry) -> bool,
+) -> Option<AuditLogEntry> {
+    fetch(ctx, guild_id, Some(action))
+        .await
+        .into_iter()
+        .find(|entry| is_recent(entry) && filter(entry))
+}
+
+// Discord reuses entries without a target and increments their count, so such an entry can only be
+// attributed when exactly one of them was incremented exactly once since the last lookup
+#[instrument(skip(ctx, filter))]
+pub async fn find_counted(
+    ctx: &Context,
+    guild_id: GuildId,
+    action: Action,
+    filter: impl Fn(&AuditLogEntry) -> bool,
+) -> Option<AuditLogEntry> {
+    let entries = fetch(ctx, guild_id, Some(action)).await;
+
+    let data = ctx.data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let mut incremented = data
+        .audit
+        .incremented(guild_id, action, entries)
+        .into_iter()
+        .filter(|entry| filter(entry));
+
+    let entry = incremented.next()?;
+
+    if incremented.next().is_some() {
+        return None;
+    }
+
+    Some(entry)
+}
+
+#[instrument(skip(ctx))]
+pub async fn find_by_target(
+    ctx: &Context,
+    guild_id: GuildId,
+    action: Action,
+    target_id: u64,
+) -> Option<AuditLogEntry> {
+    find(ctx, guild_id, action, |entry| {
+        entry.target_id.map(|id| id.get()) == Some(target_id)
+    })
+    .await
+}
+
+// Fetches the audit log once for all actions, which is cheaper than one lookup per action
+#[instrument(skip(ctx, actions))]
+pub async fn find_any_by_target(
+    ctx: &Context,
+    guild_id: GuildId,
+    actions: &[Action],
+    target_id: u64,
+) -> Option<AuditLogEntry> {
+    fetch(ctx, guild_id, None).await.into_iter().find(|entry| {
+        is_recent(entry)
+            && actions
+                .iter()
+                .any(|action| action.num() == entry.action.num())
+            && entry.target_id.map(|id| id.get()) == Some(target_id)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use serenity::all::MemberAction;
+
+    use super::*;
+
+    const ACTION: Action = Action::Member(MemberAction::MemberDisconnect);
+
+    fn entry(id: u64, count: u64) -> AuditLogEntry {
+        serde_json::from_value(json!({
+            "id": id.to_string(),
+            "action_type": ACTION.num(),
+            "user_id": "1",
+            "options": { "count": count.to_string() },
+        }))
+        .expect("Test entries are valid.")
+    }
+
+    const DISCORD_EPOCH_IN_MILLISECONDS: i64 = 1_420_070_400_000;
+
+    fn recent_id() -> u64 {
+        ((chrono::Utc::now().timestamp_millis() - DISCORD_EPOCH_IN_MILLISECONDS) as u64) << 22
+    }
+
+    fn ids(entries: Vec<AuditLogEntry>) -> Vec<u64> {
+        entries.into_iter().map(|entry| entry.id.get()).collect()
+    }
+
+    #[test]
+    fn counts_new_entries_once() {
+        let counts = AuditCounts::default();
+        let guild_id = GuildId::new(1);
+        let id = recent_id();
+
+        assert_eq!(
+            ids(counts.incremented(guild_id, ACTION, vec![entry(id, 1)])),
+            [id]
+        );
+        assert!(counts
+            .incremented(guild_id, ACTION, vec![entry(id, 1)])
+            .is_empty());
+        assert_eq!(
+            ids(counts.incremented(guild_id, ACTION, vec![entry(id, 2)])),
+            [id]
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_and_skipped_counts() {
+        let counts = AuditCounts::default();
+        let guild_id = GuildId::new(1);
+
+        assert!(counts
+            .incremented(guild_id, ACTION, vec![entry(1 << 22, 3)])
+            .is_empty());
+        assert!(counts
+            .incremented(guild_id, ACTION, vec![entry(1 << 22, 5)])
+            .is_empty());
+        assert_eq!(
+            ids(counts.incremented(guild_id, ACTION, vec![entry(1 << 22, 6)])),
+            [1 << 22]
+        );
+    }
+}
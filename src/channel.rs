@@ -1,12 +1,89 @@
-use serenity::{all::GuildId, client::Context};
+use serenity::{
+    all::{ChannelId, GuildId, Permissions},
+    client::Context,
+};
+use tracing::{debug, error, instrument};
 
-use crate::{cache::error::Error, data::Data, event::Event};
+use crate::{cache::error::Error, config::Destination, data::Data, event::Event};
 
-pub async fn get(ctx: &Context, guild_id: GuildId, event: Event) -> Result<Option<u64>, Error> {
+const REQUIRED_PERMISSIONS: Permissions = Permissions::VIEW_CHANNEL
+    .union(Permissions::SEND_MESSAGES)
+    .union(Permissions::EMBED_LINKS)
+    .union(Permissions::ATTACH_FILES);
+// Webhooks post with their own permissions, so the bot only needs to manage them
+const REQUIRED_WEBHOOK_PERMISSIONS: Permissions =
+    Permissions::VIEW_CHANNEL.union(Permissions::MANAGE_WEBHOOKS);
+
+pub async fn get(
+    ctx: &Context,
+    guild_id: GuildId,
+    event: Event,
+) -> Result<Option<Vec<Destination>>, Error> {
     let data = ctx.data.read().await;
     let data = data.get::<Data>().expect("Data should never be none.");
 
     let config = data.cache.get_or_insert(guild_id.get()).await?;
+    let destinations = config
+        .get_destinations(event.key())
+        .iter()
+        .filter(|destination| !destination.disabled)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    Ok((!destinations.is_empty()).then_some(destinations))
+}
+
+#[instrument(skip(ctx))]
+pub async fn forget(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) {
+    let data = ctx.data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let mut config = match data.cache.get_or_insert(guild_id.get()).await {
+        Ok(config) => config,
+        Err(err) => {
+            error!("Failed to get GuildConfig: {err:?}");
+            return;
+        }
+    };
+
+    if config.remove_channel(channel_id.get()) {
+        debug!(
+            "Removed deleted channel '{}' from guild '{}'.",
+            channel_id.get(),
+            guild_id.get()
+        );
+        data.cache.insert(guild_id.get(), config).await;
+    }
+}
+
+#[instrument(skip(ctx))]
+pub async fn missing_permissions(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    use_webhooks: bool,
+) -> Option<Permissions> {
+    let user_id = ctx.cache.current_user().id;
+
+    let member = match guild_id.member(ctx, user_id).await {
+        Ok(member) => member,
+        Err(err) => {
+            debug!(
+                "Failed to get own member in guild '{}': {err}",
+                guild_id.get()
+            );
+            return None;
+        }
+    };
+
+    let guild = guild_id.to_guild_cached(ctx)?;
+    let channel = guild.channels.get(&channel_id)?;
+
+    let required = if use_webhooks {
+        REQUIRED_WEBHOOK_PERMISSIONS
+    } else {
+        REQUIRED_PERMISSIONS
+    };
 
-    Ok(config.get_channel(event.key()))
+    Some(required - guild.user_permissions_in(channel, &member))
 }
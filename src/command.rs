@@ -1,106 +1,295 @@
 use poise::serenity_prelude::Channel;
-use serenity::all::ChannelType;
+use serenity::all::{ChannelType, Member, Mentionable, Role, RoleId};
 use tracing::{error, instrument};
 
-use crate::{data::Data, event::Event, messages::Messages, Context, Error};
+use crate::{
+    channel,
+    config::GuildConfig,
+    data::Data,
+    event::Event,
+    locale::Locale,
+    messages::Messages,
+    utils::{diff::DiffMode, format::Format, permissions},
+    Context, Error,
+};
+
+pub mod config;
+pub mod ignore;
+pub mod layout;
+pub mod logs;
+pub mod setup;
+pub mod text;
+pub mod webhook;
+
+#[instrument(skip(ctx, data))]
+async fn get_config(ctx: &Context<'_>, data: &Data) -> Option<(u64, GuildConfig)> {
+    let guild_id = match ctx.guild_id() {
+        Some(id) => id.get(),
+        None => {
+            ctx.not_in_guild(data).await;
+            return None;
+        }
+    };
+
+    let Some(member) = ctx.author_member().await else {
+        ctx.internal_error(data).await;
+        return None;
+    };
+
+    let config = match data.cache.get_or_insert(guild_id).await {
+        Ok(config) => config,
+        Err(err) => {
+            error!("Failed to get GuildConfig: {err:?}");
+            ctx.internal_error(data).await;
+            return None;
+        }
+    };
+
+    let is_manager = config
+        .manager_role()
+        .is_some_and(|role_id| member.roles.contains(&RoleId::new(role_id)));
+
+    if !has_manage_guild(&member) && !is_manager {
+        ctx.missing_manager_permission(data).await;
+        return None;
+    }
+
+    Some((guild_id, config))
+}
+
+fn has_manage_guild(member: &Member) -> bool {
+    member
+        .permissions
+        .is_some_and(|permissions| permissions.administrator() || permissions.manage_guild())
+}
 
 #[instrument(skip(ctx, event, channel))]
 #[poise::command(slash_command)]
-pub async fn set(ctx: Context<'_>, event: Event, channel: Option<Channel>) -> Result<(), Error> {
+pub async fn set(
+    ctx: Context<'_>,
+    event: Event,
+    channel: Channel,
+    remove: Option<bool>,
+) -> Result<(), Error> {
     let data = ctx.serenity_context().data.read().await;
     let data = data.get::<Data>().expect("Data should never be none.");
 
-    if ctx.guild_id().is_none() {
-        ctx.not_in_guild(data).await;
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
         return Ok(());
-    }
+    };
 
-    let member = match ctx.author_member().await {
-        Some(member) => member,
-        None => return Ok(()),
+    let Some(channel) = channel.guild() else {
+        ctx.internal_error(data).await;
+        return Ok(());
     };
 
-    if !member.permissions.unwrap().administrator() {
-        ctx.missing_administrator(data).await;
+    if remove.unwrap_or(false) {
+        config.remove_destination(event.key(), Some(channel.id.get()));
+        data.cache.insert(guild_id, config).await;
+        ctx.channel_unset(data).await;
         return Ok(());
     }
 
-    let guild_id = ctx
-        .guild_id()
-        .expect("None case was handled earlier.")
-        .get();
+    let kind = channel.kind;
 
-    let mut config = match data.cache.get_or_insert(guild_id).await {
-        Err(err) => {
-            error!("Failed to get GuildConfig: {err:?}");
-            ctx.internal_error(data).await;
-            return Ok(());
-        }
-        Ok(config) => config,
+    if kind != ChannelType::Voice && kind != ChannelType::Text {
+        ctx.not_valid_channel(data).await;
+        return Ok(());
+    }
+
+    let missing = channel::missing_permissions(
+        ctx.serenity_context(),
+        channel.guild_id,
+        channel.id,
+        config.use_webhooks(),
+    )
+    .await;
+
+    if let Some(missing) = missing.filter(|missing| !missing.is_empty()) {
+        ctx.missing_bot_permissions(data, &permissions::names(missing))
+            .await;
+        return Ok(());
+    }
+
+    config.add_destination(event.key(), channel.id.get());
+    data.cache.insert(guild_id, config).await;
+
+    // Like `/logs doctor`, say when the permissions couldn't be checked
+    if missing.is_none() {
+        ctx.channel_set_unchecked(data).await;
+    } else {
+        ctx.channel_set(data).await;
+    }
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, event, channel))]
+#[poise::command(slash_command)]
+pub async fn unset(ctx: Context<'_>, event: Event, channel: Option<Channel>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
     };
 
-    let id = match channel {
-        Some(channel) => {
-            let Some(channel) = channel.guild() else {
-                ctx.internal_error(data).await;
-                return Ok(());
-            };
+    config.remove_destination(event.key(), channel.map(|channel| channel.id().get()));
+    data.cache.insert(guild_id, config).await;
+    ctx.channel_unset(data).await;
 
-            let kind = channel.kind;
+    Ok(())
+}
 
-            if kind != ChannelType::Voice && kind != ChannelType::Text {
-                ctx.not_valid_channel(data).await;
-                return Ok(());
-            }
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn retention(
+    ctx: Context<'_>,
+    #[min = 0]
+    #[max = 30]
+    days: u64,
+) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
 
-            Some(channel.id.get())
-        }
-        None => None,
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
     };
 
-    config.set_channel(event.key(), id);
+    config.set_message_retention(days);
     data.cache.insert(guild_id, config).await;
-    ctx.channel_set(data).await;
+    ctx.retention_set(data, days).await;
 
     Ok(())
 }
 
-#[instrument(skip(ctx, event))]
+#[instrument(skip(ctx, mode))]
 #[poise::command(slash_command)]
-pub async fn unset(ctx: Context<'_>, event: Event) -> Result<(), Error> {
+pub async fn diff(ctx: Context<'_>, mode: DiffMode) -> Result<(), Error> {
     let data = ctx.serenity_context().data.read().await;
     let data = data.get::<Data>().expect("Data should never be none.");
 
-    let guild_id = match ctx.guild_id() {
-        Some(id) => id.get(),
-        None => {
-            ctx.not_in_guild(data).await;
-            return Ok(());
-        }
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    config.set_diff_mode(mode);
+    data.cache.insert(guild_id, config).await;
+    ctx.diff_mode_set(data).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn attachments(ctx: Context<'_>, capture: bool) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
     };
 
-    let member = match ctx.author_member().await {
-        Some(member) => member,
-        None => return Ok(()),
+    config.set_capture_attachments(capture);
+    data.cache.insert(guild_id, config).await;
+
+    if capture {
+        ctx.attachment_capture_enabled(data).await;
+    } else {
+        ctx.attachment_capture_disabled(data).await;
+    }
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, event))]
+#[poise::command(slash_command)]
+pub async fn bots(ctx: Context<'_>, event: Event, include: bool) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
     };
 
-    if !member.permissions.unwrap().administrator() {
-        ctx.missing_administrator(data).await;
+    config.set_includes_bots(event.key(), include);
+    data.cache.insert(guild_id, config).await;
+
+    if include {
+        ctx.bots_included(data).await;
+    } else {
+        ctx.bots_excluded(data).await;
+    }
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, locale))]
+#[poise::command(slash_command)]
+pub async fn language(ctx: Context<'_>, locale: Option<Locale>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
         return Ok(());
+    };
+
+    config.set_locale(locale);
+    data.cache.insert(guild_id, config).await;
+
+    if locale.is_some() {
+        ctx.language_set(data).await;
+    } else {
+        ctx.language_reset(data).await;
     }
 
-    let mut config = match data.cache.get_or_insert(guild_id).await {
-        Err(err) => {
-            error!("Failed to get GuildConfig: {err:?}");
-            ctx.internal_error(data).await;
-            return Ok(());
-        }
-        Ok(config) => config,
+    Ok(())
+}
+
+#[instrument(skip(ctx, event, format))]
+#[poise::command(slash_command)]
+pub async fn format(ctx: Context<'_>, event: Event, format: Format) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
     };
 
-    config.set_channel(event.key(), None);
+    config.set_format(event.key(), format);
     data.cache.insert(guild_id, config).await;
-    ctx.channel_unset(data).await;
+    ctx.format_set(data).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, role))]
+#[poise::command(slash_command, default_member_permissions = "MANAGE_GUILD")]
+pub async fn manager(ctx: Context<'_>, role: Option<Role>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    // Log managers can change every other setting, but shouldn't be able to hand out the role.
+    if !ctx
+        .author_member()
+        .await
+        .is_some_and(|member| has_manage_guild(&member))
+    {
+        ctx.missing_manage_guild_permission(data).await;
+        return Ok(());
+    }
+
+    config.set_manager_role(role.as_ref().map(|role| role.id.get()));
+    data.cache.insert(guild_id, config).await;
+
+    match role {
+        Some(role) => ctx.manager_set(data, &role.id.mention().to_string()).await,
+        None => ctx.manager_reset(data).await,
+    }
 
     Ok(())
 }
@@ -0,0 +1,221 @@
+use poise::ChoiceParameter;
+use serenity::all::{Attachment, ChannelId, CreateAttachment, Mentionable, RoleId};
+use tracing::{error, instrument};
+
+use super::get_config;
+use crate::{
+    config::GuildConfig,
+    data::Data,
+    event::Event,
+    messages::{guild_texts, Messages},
+    template::{self, Vars},
+    texts::Texts,
+    Context, Error,
+};
+
+const MAX_IMPORT_SIZE_IN_BYTES: u32 = 1024 * 1024;
+
+#[instrument(skip(_ctx))]
+#[poise::command(
+    slash_command,
+    subcommands("show", "export", "import"),
+    subcommand_required
+)]
+pub async fn config(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+pub fn describe_destinations(config: &GuildConfig, texts: &Texts) -> String {
+    Event::ALL
+        .iter()
+        .map(|event| {
+            let destinations = config.get_destinations(event.key());
+
+            let channels = if destinations.is_empty() {
+                texts.setup_not_logged().to_string()
+            } else {
+                destinations
+                    .iter()
+                    .map(|destination| ChannelId::new(destination.channel_id).mention().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            format!("**{}**: {channels}", event.name())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn describe_settings(config: &GuildConfig, texts: &Texts) -> String {
+    let toggle = |enabled: bool| {
+        if enabled {
+            texts.config_enabled()
+        } else {
+            texts.config_disabled()
+        }
+    };
+
+    let list = |items: Vec<String>| {
+        if items.is_empty() {
+            texts.config_none().to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+
+    let bots = Event::ALL
+        .iter()
+        .filter(|event| config.includes_bots(event.key()))
+        .map(|event| event.name().to_string())
+        .collect();
+    let formats = Event::ALL
+        .iter()
+        .filter(|event| config.formats().contains_key(event.key()))
+        .map(|event| format!("{} ({})", event.name(), config.format(event.key()).name()))
+        .collect();
+    let layouts = Event::ALL
+        .iter()
+        .filter(|event| config.layouts().contains_key(event.key()))
+        .map(|event| event.name().to_string())
+        .collect();
+
+    template::render(
+        texts.config_settings(),
+        &Vars::new()
+            .set("retention", config.message_retention().to_string())
+            .set("diff", config.diff_mode().name())
+            .set("attachments", toggle(config.capture_attachments()))
+            .set("webhooks", toggle(config.use_webhooks()))
+            .set(
+                "language",
+                config
+                    .locale()
+                    .map_or(texts.config_default_language(), |locale| locale.name()),
+            )
+            .set("bots", list(bots))
+            .set("formats", list(formats))
+            .set("layouts", list(layouts))
+            .set("texts", config.text_overrides().len().to_string())
+            .set(
+                "manager",
+                config
+                    .manager_role()
+                    .map_or(texts.config_none().to_string(), |role_id| {
+                        RoleId::new(role_id).mention().to_string()
+                    }),
+            )
+            .set(
+                "alerts",
+                config
+                    .alert_channel()
+                    .map_or(texts.alerts_owner().to_string(), |channel_id| {
+                        ChannelId::new(channel_id).mention().to_string()
+                    }),
+            )
+            .set("disable_failing", toggle(config.disable_failing())),
+    )
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn show(ctx: Context<'_>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((_, config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    let texts = guild_texts(&ctx, data).await;
+    let description = format!(
+        "{}\n{}\n\n{}",
+        texts.config_destinations(),
+        describe_destinations(&config, &texts),
+        describe_settings(&config, &texts)
+    );
+
+    ctx.config_show(data, &description).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn export(ctx: Context<'_>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    let json = match serde_json::to_vec_pretty(&config.export()) {
+        Ok(json) => json,
+        Err(err) => {
+            error!("Failed to serialize GuildConfig: {err}");
+            ctx.internal_error(data).await;
+            return Ok(());
+        }
+    };
+
+    ctx.config_exported(
+        data,
+        CreateAttachment::bytes(json, format!("config-{guild_id}.json")),
+    )
+    .await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, file))]
+#[poise::command(slash_command)]
+pub async fn import(ctx: Context<'_>, file: Attachment) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    if file.size > MAX_IMPORT_SIZE_IN_BYTES {
+        ctx.config_too_large(data, MAX_IMPORT_SIZE_IN_BYTES).await;
+        return Ok(());
+    }
+
+    let json = match file.download().await {
+        Ok(json) => json,
+        Err(err) => {
+            error!("Failed to download configuration: {err}");
+            ctx.internal_error(data).await;
+            return Ok(());
+        }
+    };
+
+    let mut imported = match GuildConfig::parse(&json, &data.texts) {
+        Ok(imported) => imported,
+        Err(err) => {
+            ctx.config_invalid(data, &err.to_string()).await;
+            return Ok(());
+        }
+    };
+
+    let skipped = match ctx.guild() {
+        Some(guild) => {
+            let exists = |channel_id| guild.channels.contains_key(&ChannelId::new(channel_id));
+
+            if !imported.alert_channel().is_none_or(exists) {
+                imported.set_alert_channel(None);
+            }
+
+            imported.retain_destinations(exists)
+        }
+        None => 0,
+    };
+
+    config.import(imported);
+    data.cache.insert(guild_id, config).await;
+    ctx.config_imported(data, skipped).await;
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use poise::{serenity_prelude::Channel, ChoiceParameter};
+use serenity::all::{Role, User};
+use tracing::instrument;
+
+use super::get_config;
+use crate::{
+    config::{Exclusions, GuildConfig},
+    data::Data,
+    event::Event,
+    ignore::ALL_EVENTS_KEY,
+    messages::{guild_texts, Messages},
+    template::{self, Vars},
+    texts::Texts,
+    Context, Error,
+};
+
+#[instrument(skip(_ctx))]
+#[poise::command(
+    slash_command,
+    subcommands("add", "remove", "list"),
+    subcommand_required
+)]
+pub async fn ignore(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[instrument(skip(ctx, channel, role, user, event, destination))]
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: Context<'_>,
+    channel: Option<Channel>,
+    role: Option<Role>,
+    user: Option<User>,
+    event: Option<Event>,
+    destination: Option<Channel>,
+) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    if channel.is_none() && role.is_none() && user.is_none() {
+        ctx.ignore_target_missing(data).await;
+        return Ok(());
+    }
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    let key = event.map_or(ALL_EVENTS_KEY, |event| event.key());
+    let destination = destination.map(|destination| destination.id().get());
+
+    let Some(mut exclusions) = get_exclusions(&config, key, destination) else {
+        ctx.ignore_destination_unknown(data).await;
+        return Ok(());
+    };
+
+    if let Some(channel) = channel {
+        exclusions.channels.insert(channel.id().get());
+    }
+
+    if let Some(role) = role {
+        exclusions.roles.insert(role.id.get());
+    }
+
+    if let Some(user) = user {
+        exclusions.users.insert(user.id.get());
+    }
+
+    set_exclusions(&mut config, key, destination, exclusions);
+    data.cache.insert(guild_id, config).await;
+    ctx.ignore_added(data).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, channel, role, user, event, destination))]
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: Context<'_>,
+    channel: Option<Channel>,
+    role: Option<Role>,
+    user: Option<User>,
+    event: Option<Event>,
+    destination: Option<Channel>,
+) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    if channel.is_none() && role.is_none() && user.is_none() {
+        ctx.ignore_target_missing(data).await;
+        return Ok(());
+    }
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    let key = event.map_or(ALL_EVENTS_KEY, |event| event.key());
+    let destination = destination.map(|destination| destination.id().get());
+
+    let Some(mut exclusions) = get_exclusions(&config, key, destination) else {
+        ctx.ignore_destination_unknown(data).await;
+        return Ok(());
+    };
+
+    if let Some(channel) = channel {
+        exclusions.channels.remove(&channel.id().get());
+    }
+
+    if let Some(role) = role {
+        exclusions.roles.remove(&role.id.get());
+    }
+
+    if let Some(user) = user {
+        exclusions.users.remove(&user.id.get());
+    }
+
+    set_exclusions(&mut config, key, destination, exclusions);
+    data.cache.insert(guild_id, config).await;
+    ctx.ignore_removed(data).await;
+
+    Ok(())
+}
+
+fn get_exclusions(config: &GuildConfig, key: &str, destination: Option<u64>) -> Option<Exclusions> {
+    match destination {
+        Some(channel_id) => config
+            .get_destination(key, channel_id)
+            .map(|destination| destination.exclusions.clone()),
+        None => Some(config.get_exclusions(key).cloned().unwrap_or_default()),
+    }
+}
+
+fn set_exclusions(
+    config: &mut GuildConfig,
+    key: &str,
+    destination: Option<u64>,
+    exclusions: Exclusions,
+) {
+    match destination {
+        Some(channel_id) => config.set_destination_exclusions(key, channel_id, exclusions),
+        None => config.set_exclusions(key, Some(exclusions)),
+    }
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((_, config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    let texts = guild_texts(&ctx, data).await;
+    let sections = describe(&config, &texts);
+
+    if sections.is_empty() {
+        ctx.ignore_list_empty(data).await;
+        return Ok(());
+    }
+
+    ctx.ignore_list(data, &sections.join("\n\n")).await;
+
+    Ok(())
+}
+
+fn describe(config: &GuildConfig, texts: &Texts) -> Vec<String> {
+    let scopes = [(ALL_EVENTS_KEY, texts.ignore_all_events())]
+        .into_iter()
+        .chain(Event::ALL.iter().map(|event| (event.key(), event.name())));
+
+    let mut sections = Vec::new();
+
+    for (key, name) in scopes {
+        let Some(exclusions) = config.get_exclusions(key) else {
+            continue;
+        };
+
+        sections.push(describe_exclusions(name, exclusions, texts));
+    }
+
+    for event in Event::ALL {
+        for destination in config.get_destinations(event.key()) {
+            if destination.exclusions.is_empty() {
+                continue;
+            }
+
+            let name = format!("{} → <#{}>", event.name(), destination.channel_id);
+            sections.push(describe_exclusions(&name, &destination.exclusions, texts));
+        }
+    }
+
+    sections
+}
+
+fn describe_exclusions(name: &str, exclusions: &Exclusions, texts: &Texts) -> String {
+    let mention = |ids: &std::collections::HashSet<u64>, prefix: &str| {
+        let mut ids = ids.iter().collect::<Vec<_>>();
+        ids.sort();
+        ids.iter()
+            .map(|id| format!("<{prefix}{id}>"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut lines = vec![format!("**{name}**")];
+
+    for (text, ids, prefix) in [
+        (texts.ignore_channels(), &exclusions.channels, "#"),
+        (texts.ignore_roles(), &exclusions.roles, "@&"),
+        (texts.ignore_users(), &exclusions.users, "@"),
+    ] {
+        if !ids.is_empty() {
+            lines.push(template::render(
+                text,
+                &Vars::new().set("targets", mention(ids, prefix)),
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,80 @@
+use tracing::instrument;
+
+use super::get_config;
+use crate::{data::Data, event::Event, layout::Layout, messages::Messages, Context, Error};
+
+#[instrument(skip(_ctx))]
+#[poise::command(
+    slash_command,
+    subcommands("set", "reset", "show"),
+    subcommand_required
+)]
+pub async fn layout(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[instrument(skip(ctx, event, json))]
+#[poise::command(slash_command)]
+pub async fn set(ctx: Context<'_>, event: Event, json: String) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let layout = match Layout::parse(&json, event) {
+        Ok(layout) => layout,
+        Err(err) => {
+            ctx.layout_invalid(data, &err.to_string()).await;
+            return Ok(());
+        }
+    };
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    config.set_layout(event.key(), Some(layout));
+    data.cache.insert(guild_id, config).await;
+    ctx.layout_set(data).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, event))]
+#[poise::command(slash_command)]
+pub async fn reset(ctx: Context<'_>, event: Event) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    config.set_layout(event.key(), None);
+    data.cache.insert(guild_id, config).await;
+    ctx.layout_reset(data).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, event))]
+#[poise::command(slash_command)]
+pub async fn show(ctx: Context<'_>, event: Event) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((_, config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    let layout = config.get_layout(event.key()).cloned().unwrap_or_default();
+    let json = serde_json::to_string_pretty(&layout).unwrap_or_default();
+    let fields = event
+        .fields()
+        .iter()
+        .map(|field| format!("`{field}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    ctx.layout_show(data, &json, &fields).await;
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use std::collections::{BTreeMap, HashSet};
+
+use poise::ChoiceParameter;
+use serenity::all::{Channel, ChannelId, ChannelType, GuildId, Mentionable};
+use tracing::instrument;
+
+use super::get_config;
+use crate::{
+    channel,
+    data::Data,
+    event::Event,
+    messages::{guild_texts, Messages},
+    template::{self, Vars},
+    utils::permissions,
+    Context, Error,
+};
+
+#[instrument(skip(_ctx))]
+#[poise::command(slash_command, subcommands("doctor", "alerts"), subcommand_required)]
+pub async fn logs(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn doctor(ctx: Context<'_>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    let guild_id = GuildId::new(guild_id);
+    let mut destinations: BTreeMap<u64, (Vec<&str>, bool)> = BTreeMap::new();
+
+    for event in Event::ALL {
+        for destination in config.get_destinations(event.key()) {
+            let (events, disabled) = destinations.entry(destination.channel_id).or_default();
+            events.push(event.name());
+            *disabled |= destination.disabled;
+        }
+    }
+
+    let Some(channels) = guild_id
+        .to_guild_cached(ctx.serenity_context())
+        .map(|guild| guild.channels.keys().copied().collect::<HashSet<_>>())
+    else {
+        ctx.doctor_unavailable(data).await;
+        return Ok(());
+    };
+
+    let texts = guild_texts(&ctx, data).await;
+    let mut problems = Vec::new();
+
+    for (channel_id, (events, disabled)) in destinations {
+        let channel_id = ChannelId::new(channel_id);
+
+        let vars = Vars::new()
+            .set("channel", channel_id.mention().to_string())
+            .set("events", events.join(", "));
+
+        if disabled {
+            problems.push(template::render(texts.doctor_disabled_channel(), &vars));
+        }
+
+        if !channels.contains(&channel_id) {
+            problems.push(template::render(texts.doctor_missing_channel(), &vars));
+            continue;
+        }
+
+        let Some(missing) = channel::missing_permissions(
+            ctx.serenity_context(),
+            guild_id,
+            channel_id,
+            config.use_webhooks(),
+        )
+        .await
+        else {
+            problems.push(template::render(texts.doctor_unchecked_channel(), &vars));
+            continue;
+        };
+
+        if !missing.is_empty() {
+            problems.push(template::render(
+                texts.doctor_missing_permissions(),
+                &vars.set("permissions", permissions::names(missing)),
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        ctx.doctor_ok(data).await;
+    } else {
+        ctx.doctor_problems(data, &problems.join("\n")).await;
+    }
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, channel))]
+#[poise::command(slash_command)]
+pub async fn alerts(
+    ctx: Context<'_>,
+    channel: Option<Channel>,
+    disable: Option<bool>,
+) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    match channel {
+        Some(channel) => {
+            let Some(channel) = channel.guild() else {
+                ctx.internal_error(data).await;
+                return Ok(());
+            };
+
+            if channel.kind != ChannelType::Voice && channel.kind != ChannelType::Text {
+                ctx.not_valid_channel(data).await;
+                return Ok(());
+            }
+
+            config.set_alert_channel(Some(channel.id.get()));
+        }
+        None if disable.is_none() => config.set_alert_channel(None),
+        None => {}
+    }
+
+    if let Some(disable) = disable {
+        config.set_disable_failing(disable);
+    }
+
+    let (alert_channel, disable_failing) = (config.alert_channel(), config.disable_failing());
+    data.cache.insert(guild_id, config).await;
+
+    let texts = guild_texts(&ctx, data).await;
+    let target = alert_channel.map_or(texts.alerts_owner().to_string(), |channel_id| {
+        ChannelId::new(channel_id).mention().to_string()
+    });
+    let disable = if disable_failing {
+        texts.config_enabled()
+    } else {
+        texts.config_disabled()
+    };
+
+    ctx.alerts_set(data, &target, disable).await;
+
+    Ok(())
+}
@@ -0,0 +1,455 @@
+use std::{collections::HashMap, time::Duration};
+
+use poise::{CreateReply, ReplyHandle};
+use serenity::all::{
+    ButtonStyle, ChannelId, ChannelType, ComponentInteraction, ComponentInteractionCollector,
+    ComponentInteractionDataKind, CreateActionRow, CreateButton, CreateChannel, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateSelectMenu,
+    CreateSelectMenuKind, GuildId, Mentionable, PermissionOverwrite, PermissionOverwriteType,
+    Permissions, RoleId,
+};
+use tracing::{error, instrument};
+
+use super::{config::describe_destinations, get_config};
+use crate::{
+    channel,
+    colors::Colors,
+    config::GuildConfig,
+    data::Data,
+    event::Event,
+    messages::guild_texts,
+    template::{self, Vars},
+    texts::Texts,
+    utils::permissions,
+    Context, Error,
+};
+
+const TIMEOUT_IN_SECONDS: u64 = 5 * 60;
+
+const GROUPS: [(&str, &[Event]); 4] = [
+    (
+        "messages",
+        &[
+            Event::MessageDelete,
+            Event::MessageUpdate,
+            Event::MessageDeleteBulk,
+        ],
+    ),
+    (
+        "members",
+        &[
+            Event::GuildMemberAddition,
+            Event::GuildMemberRemoval,
+            Event::GuildMemberUpdate,
+            Event::GuildBanAddition,
+            Event::GuildBanRemoval,
+        ],
+    ),
+    ("voice", &[Event::VoiceStateUpdate]),
+    (
+        "server",
+        &[
+            Event::ChannelCreate,
+            Event::ChannelDelete,
+            Event::ChannelUpdate,
+            Event::ThreadCreate,
+            Event::ThreadDelete,
+            Event::ThreadUpdate,
+            Event::GuildRoleCreate,
+            Event::GuildRoleDelete,
+            Event::GuildRoleUpdate,
+        ],
+    ),
+];
+
+fn group_name<'a>(texts: &'a Texts, group: &str) -> &'a str {
+    match group {
+        "messages" => texts.setup_messages(),
+        "members" => texts.setup_members(),
+        "voice" => texts.setup_voice(),
+        _ => texts.setup_server(),
+    }
+}
+
+fn embed(texts: &Texts, color: u32, description: &str) -> CreateEmbed {
+    CreateEmbed::default()
+        .color(color)
+        .title(texts.setup_title())
+        .description(description)
+}
+
+fn button(prefix: &str, id: &str, label: &str, style: ButtonStyle) -> CreateButton {
+    CreateButton::new(format!("{prefix}:{id}"))
+        .label(label)
+        .style(style)
+}
+
+fn intro(texts: &Texts, prefix: &str) -> CreateReply {
+    CreateReply::default()
+        .ephemeral(true)
+        .embed(embed(texts, Colors::PRIMARY, texts.setup_intro()))
+        .components(vec![CreateActionRow::Buttons(vec![
+            button(prefix, "create", texts.setup_create(), ButtonStyle::Primary),
+            button(prefix, "map", texts.setup_map(), ButtonStyle::Secondary),
+            button(prefix, "cancel", texts.setup_cancel(), ButtonStyle::Danger),
+        ])])
+}
+
+fn mapping(texts: &Texts, prefix: &str) -> CreateInteractionResponseMessage {
+    let mut components = GROUPS
+        .iter()
+        .map(|(group, _)| {
+            CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    format!("{prefix}:{group}"),
+                    CreateSelectMenuKind::Channel {
+                        channel_types: Some(vec![ChannelType::Text, ChannelType::Voice]),
+                        default_channels: None,
+                    },
+                )
+                .placeholder(group_name(texts, group))
+                .min_values(0)
+                .max_values(1),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    components.push(CreateActionRow::Buttons(vec![
+        button(prefix, "apply", texts.setup_apply(), ButtonStyle::Primary),
+        button(prefix, "cancel", texts.setup_cancel(), ButtonStyle::Danger),
+    ]));
+
+    CreateInteractionResponseMessage::new()
+        .embed(embed(texts, Colors::PRIMARY, texts.setup_map_prompt()))
+        .components(components)
+}
+
+#[instrument(skip(ctx, texts))]
+async fn create_channels(
+    ctx: &Context<'_>,
+    guild_id: GuildId,
+    manager_role: Option<u64>,
+    texts: &Texts,
+) -> Result<HashMap<&'static str, ChannelId>, (serenity::Error, Vec<ChannelId>)> {
+    let mut overwrites = vec![
+        PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId::new(guild_id.get())),
+        },
+        PermissionOverwrite {
+            allow: Permissions::VIEW_CHANNEL
+                | Permissions::SEND_MESSAGES
+                | Permissions::EMBED_LINKS
+                | Permissions::ATTACH_FILES
+                | Permissions::MANAGE_WEBHOOKS,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(ctx.serenity_context().cache.current_user().id),
+        },
+    ];
+
+    if let Some(role_id) = manager_role {
+        overwrites.push(PermissionOverwrite {
+            allow: Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Role(RoleId::new(role_id)),
+        });
+    }
+
+    let mut created = Vec::new();
+    let mut channels = HashMap::new();
+
+    let result = async {
+        let category = guild_id
+            .create_channel(
+                ctx.http(),
+                CreateChannel::new(texts.setup_category())
+                    .kind(ChannelType::Category)
+                    .permissions(overwrites.clone()),
+            )
+            .await?;
+
+        created.push(category.id);
+
+        for (group, _) in GROUPS {
+            let channel = guild_id
+                .create_channel(
+                    ctx.http(),
+                    CreateChannel::new(group_name(texts, group).to_lowercase())
+                        .kind(ChannelType::Text)
+                        .category(category.id)
+                        .permissions(overwrites.clone()),
+                )
+                .await?;
+
+            created.push(channel.id);
+            channels.insert(group, channel.id);
+        }
+
+        Ok::<_, serenity::Error>(())
+    }
+    .await;
+
+    let Err(err) = result else {
+        return Ok(channels);
+    };
+
+    // Remove what was created so a failed setup doesn't leave an orphan category behind
+    let mut leftover = Vec::new();
+
+    for channel_id in created.into_iter().rev() {
+        if let Err(err) = channel_id.delete(ctx.http()).await {
+            error!("Failed to delete log channel '{}': {err}", channel_id.get());
+            leftover.push(channel_id);
+        }
+    }
+
+    Err((err, leftover))
+}
+
+#[instrument(skip(ctx, config, texts, channels))]
+async fn problems(
+    ctx: &Context<'_>,
+    guild_id: GuildId,
+    config: &GuildConfig,
+    texts: &Texts,
+    channels: &HashMap<&'static str, ChannelId>,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for (group, _) in GROUPS {
+        let Some(channel_id) = channels.get(group) else {
+            continue;
+        };
+
+        let vars = Vars::new()
+            .set("channel", channel_id.mention().to_string())
+            .set("events", group_name(texts, group));
+
+        let Some(missing) = channel::missing_permissions(
+            ctx.serenity_context(),
+            guild_id,
+            *channel_id,
+            config.use_webhooks(),
+        )
+        .await
+        else {
+            problems.push(template::render(texts.doctor_unchecked_channel(), &vars));
+            continue;
+        };
+
+        if !missing.is_empty() {
+            problems.push(template::render(
+                texts.doctor_missing_permissions(),
+                &vars.set("permissions", permissions::names(missing)),
+            ));
+        }
+    }
+
+    problems
+}
+
+#[instrument(skip(data, channels))]
+async fn apply(
+    data: &Data,
+    guild_id: u64,
+    channels: &HashMap<&'static str, ChannelId>,
+) -> Option<GuildConfig> {
+    let mut config = match data.cache.get_or_insert(guild_id).await {
+        Ok(config) => config,
+        Err(err) => {
+            error!("Failed to get GuildConfig: {err:?}");
+            return None;
+        }
+    };
+
+    for (group, events) in GROUPS {
+        let Some(channel_id) = channels.get(group) else {
+            continue;
+        };
+
+        for event in events {
+            config.add_destination(event.key(), channel_id.get());
+        }
+    }
+
+    data.cache.insert(guild_id, config.clone()).await;
+
+    Some(config)
+}
+
+async fn respond(
+    ctx: &Context<'_>,
+    interaction: &ComponentInteraction,
+    response: CreateInteractionResponse,
+) {
+    if let Err(err) = interaction.create_response(ctx.http(), response).await {
+        error!("Failed to respond to interaction: {err}");
+    }
+}
+
+async fn finish(
+    ctx: &Context<'_>,
+    handle: &ReplyHandle<'_>,
+    texts: &Texts,
+    color: u32,
+    description: &str,
+) {
+    if let Err(err) = handle
+        .edit(
+            *ctx,
+            CreateReply::default()
+                .embed(embed(texts, color, description))
+                .components(Vec::new()),
+        )
+        .await
+    {
+        error!("Failed to edit message: {err}");
+    }
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn setup(ctx: Context<'_>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    let manager_role = config.manager_role();
+
+    let texts = guild_texts(&ctx, data).await;
+    let prefix = ctx.id().to_string();
+
+    let handle = match ctx.send(intro(&texts, &prefix)).await {
+        Ok(handle) => handle,
+        Err(err) => {
+            error!("Failed to send message: {err}");
+            return Ok(());
+        }
+    };
+
+    let mut channels = HashMap::new();
+
+    loop {
+        let filter_prefix = prefix.clone();
+
+        let Some(interaction) = ComponentInteractionCollector::new(ctx.serenity_context())
+            .author_id(ctx.author().id)
+            .filter(move |interaction| interaction.data.custom_id.starts_with(&filter_prefix))
+            .timeout(Duration::from_secs(TIMEOUT_IN_SECONDS))
+            .await
+        else {
+            finish(&ctx, &handle, &texts, Colors::ERROR, texts.setup_timeout()).await;
+            return Ok(());
+        };
+
+        let id = interaction
+            .data
+            .custom_id
+            .trim_start_matches(&prefix)
+            .trim_start_matches(':');
+
+        match id {
+            "map" => {
+                respond(
+                    &ctx,
+                    &interaction,
+                    CreateInteractionResponse::UpdateMessage(mapping(&texts, &prefix)),
+                )
+                .await;
+            }
+            "create" => {
+                respond(&ctx, &interaction, CreateInteractionResponse::Acknowledge).await;
+
+                match create_channels(&ctx, GuildId::new(guild_id), manager_role, &texts).await {
+                    Ok(created) => {
+                        channels = created;
+                        break;
+                    }
+                    Err((err, leftover)) => {
+                        error!("Failed to create log channels: {err}");
+                        let mut description = template::render(
+                            texts.setup_failed(),
+                            &Vars::new().set("error", err.to_string()),
+                        );
+
+                        if !leftover.is_empty() {
+                            let leftover = leftover
+                                .iter()
+                                .map(|channel_id| channel_id.mention().to_string())
+                                .collect::<Vec<_>>()
+                                .join(", ");
+
+                            description.push('\n');
+                            description.push_str(&template::render(
+                                texts.setup_leftover(),
+                                &Vars::new().set("channels", leftover),
+                            ));
+                        }
+
+                        finish(&ctx, &handle, &texts, Colors::ERROR, &description).await;
+                        return Ok(());
+                    }
+                }
+            }
+            "apply" => {
+                respond(&ctx, &interaction, CreateInteractionResponse::Acknowledge).await;
+                break;
+            }
+            "cancel" => {
+                respond(&ctx, &interaction, CreateInteractionResponse::Acknowledge).await;
+                finish(
+                    &ctx,
+                    &handle,
+                    &texts,
+                    Colors::ERROR,
+                    texts.setup_cancelled(),
+                )
+                .await;
+                return Ok(());
+            }
+            group => {
+                if let ComponentInteractionDataKind::ChannelSelect { values } =
+                    &interaction.data.kind
+                {
+                    if let Some((group, _)) = GROUPS.iter().find(|(name, _)| *name == group) {
+                        match values.first() {
+                            Some(channel_id) => channels.insert(*group, *channel_id),
+                            None => channels.remove(group),
+                        };
+                    }
+                }
+
+                respond(&ctx, &interaction, CreateInteractionResponse::Acknowledge).await;
+            }
+        }
+    }
+
+    let Some(config) = apply(data, guild_id, &channels).await else {
+        finish(&ctx, &handle, &texts, Colors::ERROR, texts.internal_error()).await;
+        return Ok(());
+    };
+
+    let mut description = format!(
+        "{}\n{}",
+        texts.setup_summary(),
+        describe_destinations(&config, &texts)
+    );
+
+    let problems = problems(&ctx, GuildId::new(guild_id), &config, &texts, &channels).await;
+
+    if !problems.is_empty() {
+        description.push_str("\n\n");
+        description.push_str(&template::render(
+            texts.doctor_problems(),
+            &Vars::new().set("problems", problems.join("\n")),
+        ));
+    }
+
+    finish(&ctx, &handle, &texts, Colors::PRIMARY, &description).await;
+
+    Ok(())
+}
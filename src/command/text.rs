@@ -0,0 +1,111 @@
+use tracing::instrument;
+
+use super::get_config;
+use crate::{data::Data, messages::Messages, template, texts, Context, Error};
+
+const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+
+async fn autocomplete_key(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    data.texts
+        .keys()
+        .into_iter()
+        .filter(|key| key.contains(partial))
+        .take(MAX_AUTOCOMPLETE_CHOICES)
+        .collect()
+}
+
+#[instrument(skip(_ctx))]
+#[poise::command(
+    slash_command,
+    subcommands("set", "reset", "preview"),
+    subcommand_required
+)]
+pub async fn text(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[instrument(skip(ctx, value))]
+#[poise::command(slash_command)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_key"] key: String,
+    value: String,
+) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    if data.texts.text(&key).is_none() {
+        ctx.text_unknown_key(data).await;
+        return Ok(());
+    }
+
+    let value = value.replace("\\n", "\n");
+
+    if let Err(err) = template::validate(&value, texts::variables(&key)) {
+        ctx.text_invalid(data, &err.to_string()).await;
+        return Ok(());
+    }
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    config.set_text_override(&key, Some(value));
+    data.cache.insert(guild_id, config).await;
+    ctx.text_set(data).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn reset(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_key"] key: Option<String>,
+) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    match key {
+        Some(key) => config.set_text_override(&key, None),
+        None => config.clear_text_overrides(),
+    }
+
+    data.cache.insert(guild_id, config).await;
+    ctx.text_reset(data).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn preview(
+    ctx: Context<'_>,
+    #[autocomplete = "autocomplete_key"] key: String,
+) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, _)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    let Some(text) = texts::for_guild(ctx.serenity_context(), Some(guild_id.into()), &data.texts)
+        .await
+        .text(&key)
+    else {
+        ctx.text_unknown_key(data).await;
+        return Ok(());
+    };
+
+    ctx.text_preview(data, &key, &text).await;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use serenity::all::WebhookId;
+use tracing::{debug, instrument};
+
+use super::get_config;
+use crate::{
+    config::WebhookIdentity, data::Data, event::Event, messages::Messages, Context, Error,
+};
+
+#[instrument(skip(_ctx))]
+#[poise::command(
+    slash_command,
+    subcommands("enable", "disable", "identity"),
+    subcommand_required
+)]
+pub async fn webhook(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    config.set_use_webhooks(true);
+    data.cache.insert(guild_id, config).await;
+    ctx.webhooks_enabled(data).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx))]
+#[poise::command(slash_command)]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    config.set_use_webhooks(false);
+    let webhooks = config.take_webhooks();
+
+    // Saved before the deletes so changes made while they run aren't overwritten
+    data.cache.insert(guild_id, config).await;
+
+    for webhook in webhooks {
+        if let Err(err) = ctx
+            .http()
+            .delete_webhook_with_token(WebhookId::new(webhook.id), &webhook.token, None)
+            .await
+        {
+            debug!("Failed to delete webhook '{}': {err}", webhook.id);
+        }
+    }
+
+    ctx.webhooks_disabled(data).await;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx, event))]
+#[poise::command(slash_command)]
+pub async fn identity(
+    ctx: Context<'_>,
+    event: Event,
+    #[max_length = 80] username: Option<String>,
+    avatar_url: Option<String>,
+) -> Result<(), Error> {
+    let data = ctx.serenity_context().data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let Some((guild_id, mut config)) = get_config(&ctx, data).await else {
+        return Ok(());
+    };
+
+    if username.is_none() && avatar_url.is_none() {
+        config.set_webhook_identity(event.key(), None);
+        data.cache.insert(guild_id, config).await;
+        ctx.webhook_identity_reset(data).await;
+        return Ok(());
+    }
+
+    config.set_webhook_identity(
+        event.key(),
+        Some(WebhookIdentity {
+            username,
+            avatar_url,
+        }),
+    );
+    data.cache.insert(guild_id, config).await;
+    ctx.webhook_identity_set(data).await;
+
+    Ok(())
+}
@@ -1,12 +1,146 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use tracing::instrument;
 
+use self::error::Error;
+use crate::{
+    event::Event,
+    layout::Layout,
+    locale::Locale,
+    template,
+    texts::{self, Texts},
+    utils::{diff::DiffMode, format::Format},
+};
+
+pub mod error;
+
+const DEFAULT_MESSAGE_RETENTION_IN_DAYS: u64 = 7;
+
+fn default_message_retention() -> u64 {
+    DEFAULT_MESSAGE_RETENTION_IN_DAYS
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct StoredWebhook {
+    #[serde(rename = "i")]
+    pub id: u64,
+    #[serde(rename = "t")]
+    pub token: String,
+}
+
+impl fmt::Debug for StoredWebhook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoredWebhook")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct WebhookIdentity {
+    #[serde(rename = "n", default)]
+    pub username: Option<String>,
+    #[serde(rename = "a", default)]
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Exclusions {
+    #[serde(rename = "c", default)]
+    pub channels: HashSet<u64>,
+    #[serde(rename = "r", default)]
+    pub roles: HashSet<u64>,
+    #[serde(rename = "u", default)]
+    pub users: HashSet<u64>,
+}
+
+impl Exclusions {
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty() && self.roles.is_empty() && self.users.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Destination {
+    #[serde(rename = "i")]
+    pub channel_id: u64,
+    #[serde(rename = "x", default)]
+    pub exclusions: Exclusions,
+    #[serde(rename = "d", default)]
+    pub disabled: bool,
+}
+
+// Configs written before multiple destinations stored a single channel id per event
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredDestinations {
+    Single(u64),
+    Multiple(Vec<Destination>),
+}
+
+fn deserialize_destinations<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, Vec<Destination>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let stored = HashMap::<String, StoredDestinations>::deserialize(deserializer)?;
+
+    Ok(stored
+        .into_iter()
+        .map(|(key, destinations)| {
+            let destinations = match destinations {
+                StoredDestinations::Single(channel_id) => vec![Destination {
+                    channel_id,
+                    exclusions: Exclusions::default(),
+                    disabled: false,
+                }],
+                StoredDestinations::Multiple(destinations) => destinations,
+            };
+
+            (key, destinations)
+        })
+        .collect())
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildConfig {
-    #[serde(rename = "c")]
-    channels: HashMap<String, u64>,
+    #[serde(rename = "c", deserialize_with = "deserialize_destinations")]
+    channels: HashMap<String, Vec<Destination>>,
+    #[serde(rename = "r", default = "default_message_retention")]
+    message_retention: u64,
+    #[serde(rename = "w", default)]
+    use_webhooks: bool,
+    #[serde(rename = "h", default)]
+    webhooks: HashMap<u64, StoredWebhook>,
+    #[serde(rename = "i", default)]
+    webhook_identities: HashMap<String, WebhookIdentity>,
+    #[serde(rename = "d", default)]
+    diff_mode: DiffMode,
+    #[serde(rename = "a", default)]
+    capture_attachments: bool,
+    #[serde(rename = "x", default)]
+    exclusions: HashMap<String, Exclusions>,
+    #[serde(rename = "b", default)]
+    bot_events: HashSet<String>,
+    #[serde(rename = "t", default)]
+    texts: BTreeMap<String, String>,
+    #[serde(rename = "l", default)]
+    locale: Option<Locale>,
+    #[serde(rename = "y", default)]
+    layouts: HashMap<String, Layout>,
+    #[serde(rename = "o", default)]
+    formats: HashMap<String, Format>,
+    #[serde(rename = "m", default)]
+    manager_role: Option<u64>,
+    #[serde(rename = "n", default)]
+    alert_channel: Option<u64>,
+    #[serde(rename = "f", default)]
+    disable_failing: bool,
 
     #[serde(skip_serializing, skip_deserializing)]
     has_changed: bool,
@@ -19,22 +153,427 @@ impl GuildConfig {
     }
 
     #[instrument(skip(self))]
-    pub fn get_channel(&self, key: &str) -> Option<u64> {
-        self.channels.get(key).copied()
+    pub fn get_destinations(&self, key: &str) -> &[Destination] {
+        self.channels.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_destination(&self, key: &str, channel_id: u64) -> Option<&Destination> {
+        self.get_destinations(key)
+            .iter()
+            .find(|destination| destination.channel_id == channel_id)
+    }
+
+    #[instrument(skip(self))]
+    pub fn add_destination(&mut self, key: &str, channel_id: u64) {
+        self.has_changed = true;
+
+        let destinations = self.channels.entry(key.to_string()).or_default();
+
+        match destinations
+            .iter_mut()
+            .find(|destination| destination.channel_id == channel_id)
+        {
+            Some(destination) => destination.disabled = false,
+            None => destinations.push(Destination {
+                channel_id,
+                exclusions: Exclusions::default(),
+                disabled: false,
+            }),
+        }
     }
 
     #[instrument(skip(self))]
-    pub fn set_channel(&mut self, key: &str, channel_id: Option<u64>) {
+    pub fn remove_destination(&mut self, key: &str, channel_id: Option<u64>) {
         self.has_changed = true;
 
-        if channel_id.is_none() {
+        let Some(channel_id) = channel_id else {
             self.channels.remove(key);
             return;
+        };
+
+        if let Some(destinations) = self.channels.get_mut(key) {
+            destinations.retain(|destination| destination.channel_id != channel_id);
+
+            if destinations.is_empty() {
+                self.channels.remove(key);
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_destination_exclusions(
+        &mut self,
+        key: &str,
+        channel_id: u64,
+        exclusions: Exclusions,
+    ) {
+        let destination = self.channels.get_mut(key).and_then(|destinations| {
+            destinations
+                .iter_mut()
+                .find(|destination| destination.channel_id == channel_id)
+        });
+
+        if let Some(destination) = destination {
+            self.has_changed = true;
+            destination.exclusions = exclusions;
+        }
+    }
+
+    #[instrument(skip(self, exists))]
+    pub fn retain_destinations(&mut self, exists: impl Fn(u64) -> bool) -> usize {
+        let mut removed = 0;
+
+        for destinations in self.channels.values_mut() {
+            let count = destinations.len();
+            destinations.retain(|destination| exists(destination.channel_id));
+            removed += count - destinations.len();
+        }
+
+        if removed > 0 {
+            self.has_changed = true;
+            self.channels
+                .retain(|_, destinations| !destinations.is_empty());
+        }
+
+        removed
+    }
+
+    #[instrument(skip(self))]
+    pub fn is_destination(&self, channel_id: u64) -> bool {
+        self.channels
+            .values()
+            .flatten()
+            .any(|destination| destination.channel_id == channel_id)
+    }
+
+    #[instrument(skip(self))]
+    pub fn disable_destination(&mut self, channel_id: u64) -> bool {
+        let mut disabled = false;
+
+        for destination in self.channels.values_mut().flatten() {
+            if destination.channel_id == channel_id && !destination.disabled {
+                destination.disabled = true;
+                disabled = true;
+            }
+        }
+
+        self.has_changed |= disabled;
+        disabled
+    }
+
+    #[instrument(skip(self))]
+    pub fn remove_channel(&mut self, channel_id: u64) -> bool {
+        let mut removed = self.retain_destinations(|id| id != channel_id) > 0;
+        removed |= self.webhooks.remove(&channel_id).is_some();
+
+        if self.alert_channel == Some(channel_id) {
+            self.alert_channel = None;
+            removed = true;
         }
 
-        self.channels.insert(
-            key.to_string(),
-            channel_id.expect("None case was handled earlier."),
-        );
+        self.has_changed |= removed;
+        removed
+    }
+
+    #[instrument(skip(self))]
+    pub fn message_retention(&self) -> u64 {
+        self.message_retention
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_message_retention(&mut self, days: u64) {
+        self.has_changed = true;
+        self.message_retention = days;
+    }
+
+    #[instrument(skip(self))]
+    pub fn use_webhooks(&self) -> bool {
+        self.use_webhooks
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_use_webhooks(&mut self, use_webhooks: bool) {
+        self.has_changed = true;
+        self.use_webhooks = use_webhooks;
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_webhook(&self, channel_id: u64) -> Option<&StoredWebhook> {
+        self.webhooks.get(&channel_id)
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_webhook(&mut self, channel_id: u64, webhook: Option<StoredWebhook>) {
+        self.has_changed = true;
+
+        match webhook {
+            Some(webhook) => self.webhooks.insert(channel_id, webhook),
+            None => self.webhooks.remove(&channel_id),
+        };
+    }
+
+    #[instrument(skip(self))]
+    pub fn take_webhooks(&mut self) -> Vec<StoredWebhook> {
+        self.has_changed = true;
+        self.webhooks.drain().map(|(_, webhook)| webhook).collect()
+    }
+
+    #[instrument(skip(self))]
+    pub fn is_own_webhook(&self, webhook_id: u64) -> bool {
+        self.webhooks
+            .values()
+            .any(|webhook| webhook.id == webhook_id)
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_webhook_identity(&self, key: &str) -> Option<&WebhookIdentity> {
+        self.webhook_identities.get(key)
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_webhook_identity(&mut self, key: &str, identity: Option<WebhookIdentity>) {
+        self.has_changed = true;
+
+        match identity {
+            Some(identity) => self.webhook_identities.insert(key.to_string(), identity),
+            None => self.webhook_identities.remove(key),
+        };
+    }
+
+    #[instrument(skip(self))]
+    pub fn diff_mode(&self) -> DiffMode {
+        self.diff_mode
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_diff_mode(&mut self, diff_mode: DiffMode) {
+        self.has_changed = true;
+        self.diff_mode = diff_mode;
+    }
+
+    #[instrument(skip(self))]
+    pub fn capture_attachments(&self) -> bool {
+        self.capture_attachments
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_capture_attachments(&mut self, capture_attachments: bool) {
+        self.has_changed = true;
+        self.capture_attachments = capture_attachments;
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_exclusions(&self, key: &str) -> Option<&Exclusions> {
+        self.exclusions.get(key)
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_exclusions(&mut self, key: &str, exclusions: Option<Exclusions>) {
+        self.has_changed = true;
+
+        match exclusions.filter(|exclusions| !exclusions.is_empty()) {
+            Some(exclusions) => self.exclusions.insert(key.to_string(), exclusions),
+            None => self.exclusions.remove(key),
+        };
+    }
+
+    #[instrument(skip(self))]
+    pub fn includes_bots(&self, key: &str) -> bool {
+        self.bot_events.contains(key)
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_includes_bots(&mut self, key: &str, include: bool) {
+        self.has_changed = true;
+
+        if include {
+            self.bot_events.insert(key.to_string());
+        } else {
+            self.bot_events.remove(key);
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub fn text_overrides(&self) -> &BTreeMap<String, String> {
+        &self.texts
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_text_override(&mut self, key: &str, text: Option<String>) {
+        self.has_changed = true;
+
+        match text {
+            Some(text) => self.texts.insert(key.to_string(), text),
+            None => self.texts.remove(key),
+        };
+    }
+
+    #[instrument(skip(self))]
+    pub fn clear_text_overrides(&mut self) {
+        self.has_changed = true;
+        self.texts.clear();
+    }
+
+    #[instrument(skip(self))]
+    pub fn locale(&self) -> Option<Locale> {
+        self.locale
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_locale(&mut self, locale: Option<Locale>) {
+        self.has_changed = true;
+        self.locale = locale;
+    }
+
+    #[instrument(skip(self))]
+    pub fn get_layout(&self, key: &str) -> Option<&Layout> {
+        self.layouts.get(key)
+    }
+
+    #[instrument(skip(self))]
+    pub fn layouts(&self) -> &HashMap<String, Layout> {
+        &self.layouts
+    }
+
+    #[instrument(skip(self, layout))]
+    pub fn set_layout(&mut self, key: &str, layout: Option<Layout>) {
+        self.has_changed = true;
+
+        match layout {
+            Some(layout) => self.layouts.insert(key.to_string(), layout),
+            None => self.layouts.remove(key),
+        };
+    }
+
+    #[instrument(skip(self))]
+    pub fn formats(&self) -> &HashMap<String, Format> {
+        &self.formats
+    }
+
+    #[instrument(skip(self))]
+    pub fn format(&self, key: &str) -> Format {
+        self.formats.get(key).copied().unwrap_or_default()
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_format(&mut self, key: &str, format: Format) {
+        self.has_changed = true;
+
+        if format == Format::default() {
+            self.formats.remove(key);
+        } else {
+            self.formats.insert(key.to_string(), format);
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub fn manager_role(&self) -> Option<u64> {
+        self.manager_role
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_manager_role(&mut self, role_id: Option<u64>) {
+        self.has_changed = true;
+        self.manager_role = role_id;
+    }
+
+    #[instrument(skip(self))]
+    pub fn alert_channel(&self) -> Option<u64> {
+        self.alert_channel
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_alert_channel(&mut self, channel_id: Option<u64>) {
+        self.has_changed = true;
+        self.alert_channel = channel_id;
+    }
+
+    #[instrument(skip(self))]
+    pub fn disable_failing(&self) -> bool {
+        self.disable_failing
+    }
+
+    #[instrument(skip(self))]
+    pub fn set_disable_failing(&mut self, disable: bool) {
+        self.has_changed = true;
+        self.disable_failing = disable;
+    }
+
+    #[instrument(skip(self))]
+    pub fn export(&self) -> GuildConfig {
+        let mut config = self.clone();
+        config.webhooks.clear();
+        config
+    }
+
+    #[instrument(skip(json, texts))]
+    pub fn parse(json: &[u8], texts: &Texts) -> Result<GuildConfig, Error> {
+        let config: GuildConfig = serde_json::from_slice(json).map_err(Error::Json)?;
+
+        let keys = config
+            .channels
+            .keys()
+            .chain(config.webhook_identities.keys())
+            .chain(config.bot_events.iter())
+            .chain(config.formats.keys())
+            .chain(config.layouts.keys());
+
+        for key in keys {
+            if Event::from_key(key).is_none() {
+                return Err(Error::UnknownEvent(key.clone()));
+            }
+        }
+
+        for (key, text) in &config.texts {
+            if texts.text(key).is_none() {
+                return Err(Error::UnknownText(key.clone()));
+            }
+
+            template::validate(text, texts::variables(key))
+                .map_err(|err| Error::Text(key.clone(), err))?;
+        }
+
+        for (key, layout) in &config.layouts {
+            let event = Event::from_key(key).expect("Layout keys were validated earlier.");
+
+            layout
+                .validate(event)
+                .map_err(|err| Error::Layout(key.clone(), err))?;
+        }
+
+        Ok(config)
+    }
+
+    #[instrument(skip(self, config))]
+    pub fn import(&mut self, mut config: GuildConfig) {
+        config.webhooks = std::mem::take(&mut self.webhooks);
+        config.manager_role = self.manager_role;
+        config.has_changed = true;
+        *self = config;
+    }
+}
+
+impl Default for GuildConfig {
+    fn default() -> Self {
+        Self {
+            channels: HashMap::new(),
+            message_retention: DEFAULT_MESSAGE_RETENTION_IN_DAYS,
+            use_webhooks: false,
+            webhooks: HashMap::new(),
+            webhook_identities: HashMap::new(),
+            diff_mode: DiffMode::default(),
+            capture_attachments: false,
+            exclusions: HashMap::new(),
+            bot_events: HashSet::new(),
+            texts: BTreeMap::new(),
+            locale: None,
+            layouts: HashMap::new(),
+            formats: HashMap::new(),
+            manager_role: None,
+            alert_channel: None,
+            disable_failing: false,
+            has_changed: false,
+        }
     }
 }
@@ -0,0 +1,22 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Json(serde_json::Error),
+    UnknownEvent(String),
+    UnknownText(String),
+    Text(String, crate::template::error::Error),
+    Layout(String, crate::layout::error::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "{err}"),
+            Self::UnknownEvent(key) => write!(f, "unknown event '{key}'"),
+            Self::UnknownText(key) => write!(f, "unknown text '{key}'"),
+            Self::Text(key, err) => write!(f, "text '{key}': {err}"),
+            Self::Layout(key, err) => write!(f, "layout '{key}': {err}"),
+        }
+    }
+}
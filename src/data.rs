@@ -1,9 +1,16 @@
-use std::{env::current_dir, fs, path::PathBuf, sync::Arc};
+use std::{collections::HashMap, env::current_dir, fs, path::PathBuf, sync::Arc};
 
 use serenity::prelude::TypeMapKey;
 use tracing::{error, info};
 
-use crate::{cache::GuildConfigCache, texts::Texts};
+use crate::{
+    audit::AuditCounts,
+    cache::GuildConfigCache,
+    delivery::queue::DeliveryQueue,
+    locale::{self, Locale},
+    store::MessageStore,
+    texts::Texts,
+};
 
 use self::error::Error;
 
@@ -11,12 +18,20 @@ pub mod error;
 
 pub struct Data {
     pub cache: GuildConfigCache,
+    pub store: MessageStore,
+    pub queue: DeliveryQueue,
+    pub audit: AuditCounts,
     pub texts: Texts,
+    pub translations: HashMap<Locale, Texts>,
 }
 
 impl Data {
     pub fn new() -> Result<Data, Error> {
         let cache = GuildConfigCache::default();
+        let store = MessageStore::default();
+        let queue = DeliveryQueue::default();
+        let audit = AuditCounts::default();
+        let translations = locale::load_translations()?;
 
         info!("Loading texts...");
 
@@ -37,7 +52,14 @@ impl Data {
 
             Self::save_texts(&texts)?;
 
-            return Ok(Self { cache, texts });
+            return Ok(Self {
+                cache,
+                store,
+                queue,
+                audit,
+                texts,
+                translations,
+            });
         }
 
         let raw_data = match fs::read_to_string(&path) {
@@ -56,7 +78,22 @@ impl Data {
             }
         };
 
-        Ok(Self { cache, texts })
+        if let Err(errors) = texts.validate() {
+            for (key, err) in &errors {
+                error!("Invalid text '{key}' in texts.json: {err}");
+            }
+
+            return Err(Error::Template(errors));
+        }
+
+        Ok(Self {
+            cache,
+            store,
+            queue,
+            audit,
+            texts,
+            translations,
+        })
     }
 
     pub fn save_texts(texts: &Texts) -> Result<(), Error> {
@@ -4,6 +4,7 @@
 pub enum Error {
     Serialization(serde_json::Error),
     Deserialization(serde_json::Error),
+    Template(Vec<(String, crate::template::error::Error)>),
     Writing(std::io::Error),
     Reading(std::io::Error),
 }
@@ -0,0 +1,323 @@
+use serenity::{
+    all::{
+        ChannelId, CreateAllowedMentions, CreateAttachment, CreateMessage, CreateWebhook,
+        ExecuteWebhook, GuildId, HttpError, Mentionable, WebhookId,
+    },
+    builder::{Builder, CreateEmbed},
+    client::Context,
+};
+use tracing::{debug, error, instrument};
+
+use self::{
+    error::Error,
+    queue::{QueuedEntry, QueuedFile},
+};
+use crate::{
+    colors::Colors,
+    config::StoredWebhook,
+    data::Data,
+    event::Event,
+    layout::LogEmbed,
+    template::{self, Vars},
+    texts,
+    utils::format::Format,
+};
+
+pub mod error;
+pub mod queue;
+
+const UNKNOWN_WEBHOOK_ERROR_CODE: isize = 10015;
+
+#[instrument(skip(ctx))]
+async fn create_webhook(ctx: &Context, channel_id: ChannelId) -> Result<StoredWebhook, Error> {
+    let name = ctx.cache.current_user().name.clone();
+
+    let webhook = match channel_id
+        .create_webhook(&ctx.http, CreateWebhook::new(name))
+        .await
+    {
+        Ok(webhook) => webhook,
+        Err(err) => {
+            error!("Failed to create webhook: {err}");
+            return Err(err.into());
+        }
+    };
+
+    let Some(token) = webhook
+        .url()
+        .ok()
+        .and_then(|url| url.rsplit('/').next().map(str::to_string))
+    else {
+        error!("Created webhook didn't have a token.");
+        return Err(serenity::Error::Other("Created webhook didn't have a token.").into());
+    };
+
+    Ok(StoredWebhook {
+        id: webhook.id.get(),
+        token,
+    })
+}
+
+#[instrument(skip(ctx, embeds, files))]
+pub async fn send(
+    ctx: &Context,
+    guild_id: GuildId,
+    event: Event,
+    destinations: &[u64],
+    embeds: Vec<LogEmbed>,
+    files: Vec<CreateAttachment>,
+) {
+    let (destinations, missing): (Vec<u64>, Vec<u64>) = {
+        let Some(guild) = guild_id.to_guild_cached(ctx) else {
+            debug!("Failed to get cached guild '{}'.", guild_id.get());
+            return;
+        };
+
+        destinations
+            .iter()
+            .partition(|channel_id| guild.channels.contains_key(&ChannelId::new(**channel_id)))
+    };
+
+    let data = ctx.data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    // Channels deleted while the bot was offline are only noticed here
+    for channel_id in missing {
+        debug!(
+            "Failed to get guild '{}' channel '{}'.",
+            guild_id.get(),
+            channel_id
+        );
+        data.queue.fail(ctx, guild_id.get(), channel_id).await;
+    }
+
+    if destinations.is_empty() {
+        return;
+    }
+
+    let (layout, format) = match data.cache.get_or_insert(guild_id.get()).await {
+        Ok(config) => (
+            config.get_layout(event.key()).cloned(),
+            config.format(event.key()),
+        ),
+        Err(err) => {
+            error!("Failed to get GuildConfig: {err:?}");
+            (None, Format::default())
+        }
+    };
+
+    let embeds = embeds
+        .into_iter()
+        .map(|embed| embed.build(layout.as_ref()))
+        .collect();
+
+    let mut entry = match QueuedEntry::new(guild_id.get(), event, format, embeds, files) {
+        Ok(entry) => entry,
+        Err(err) => {
+            error!("Failed to create queued entry: {err:?}");
+            return;
+        }
+    };
+
+    for channel_id in destinations {
+        match entry.copy() {
+            Ok(copy) => data.queue.push(ctx, channel_id, vec![copy]),
+            Err(err) => error!("Failed to copy queued entry: {err:?}"),
+        }
+    }
+
+    entry.remove_files();
+}
+
+// The config is read again so changes made while a request was running aren't reverted
+#[instrument(skip(data, webhook))]
+async fn store_webhook(
+    data: &Data,
+    guild_id: u64,
+    channel_id: u64,
+    webhook: Option<StoredWebhook>,
+) {
+    match data.cache.get_or_insert(guild_id).await {
+        Ok(mut config) => {
+            config.set_webhook(channel_id, webhook);
+            data.cache.insert(guild_id, config).await;
+        }
+        Err(err) => error!("Failed to get GuildConfig: {err:?}"),
+    }
+}
+
+#[instrument(skip(ctx, batch))]
+async fn transmit(ctx: &Context, channel_id: u64, batch: &[QueuedEntry]) -> Result<(), Error> {
+    let Some(first) = batch.first() else {
+        return Ok(());
+    };
+
+    let (guild_id, event) = (first.guild_id, first.event);
+    let channel_id = ChannelId::new(channel_id);
+
+    let embeds = batch
+        .iter()
+        .flat_map(|entry| entry.embeds.iter().cloned().map(CreateEmbed::from))
+        .collect::<Vec<_>>();
+    let content = batch
+        .iter()
+        .filter_map(|entry| entry.content.as_deref())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let files = batch
+        .iter()
+        .flat_map(|entry| entry.files.iter().filter_map(QueuedFile::load))
+        .collect::<Vec<CreateAttachment>>();
+
+    let data = ctx.data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let config = data
+        .cache
+        .get_or_insert(guild_id)
+        .await
+        .map_err(Error::Config)?;
+
+    if !config.use_webhooks() {
+        let mut message = CreateMessage::default()
+            .embeds(embeds)
+            .add_files(files)
+            .allowed_mentions(CreateAllowedMentions::new());
+
+        if !content.is_empty() {
+            message = message.content(content);
+        }
+
+        channel_id.send_message(&ctx.http, message).await?;
+
+        return Ok(());
+    }
+
+    // Only the channel's queue worker transmits, so a missing webhook is created once.
+    let webhook = match config.get_webhook(channel_id.get()) {
+        Some(webhook) => webhook.clone(),
+        None => {
+            let webhook = create_webhook(ctx, channel_id).await?;
+
+            store_webhook(data, guild_id, channel_id.get(), Some(webhook.clone())).await;
+            webhook
+        }
+    };
+
+    let (username, avatar_url) = {
+        let current_user = ctx.cache.current_user();
+        let identity = config.get_webhook_identity(event.key());
+
+        (
+            identity
+                .and_then(|identity| identity.username.clone())
+                .unwrap_or(current_user.name.clone()),
+            identity
+                .and_then(|identity| identity.avatar_url.clone())
+                .unwrap_or(current_user.face()),
+        )
+    };
+
+    let mut builder = ExecuteWebhook::new()
+        .username(username)
+        .avatar_url(avatar_url)
+        .embeds(embeds)
+        .add_files(files)
+        .allowed_mentions(CreateAllowedMentions::new());
+
+    if !content.is_empty() {
+        builder = builder.content(content);
+    }
+
+    if let Err(err) = builder
+        .execute(
+            &ctx.http,
+            (WebhookId::new(webhook.id), &webhook.token, false),
+        )
+        .await
+    {
+        if let serenity::Error::Http(HttpError::UnsuccessfulRequest(response)) = &err {
+            if response.error.code == UNKNOWN_WEBHOOK_ERROR_CODE {
+                debug!("Forgetting deleted webhook '{}'.", webhook.id);
+                store_webhook(data, guild_id, channel_id.get(), None).await;
+            }
+        }
+
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+// Returns whether the alert no longer has to be sent
+#[instrument(skip(ctx))]
+async fn report_unusable(ctx: &Context, guild_id: u64, channel_id: u64, failures: u32) -> bool {
+    let data = ctx.data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let mut config = match data.cache.get_or_insert(guild_id).await {
+        Ok(config) => config,
+        Err(err) => {
+            error!("Failed to get GuildConfig: {err:?}");
+            return false;
+        }
+    };
+
+    if !config.is_destination(channel_id) {
+        debug!("Channel '{channel_id}' is no longer a log channel.");
+        return true;
+    }
+
+    let disabled = config.disable_failing();
+    let alert_channel = config.alert_channel().filter(|id| *id != channel_id);
+
+    if disabled && config.disable_destination(channel_id) {
+        data.cache.insert(guild_id, config).await;
+    }
+
+    let guild_id = GuildId::new(guild_id);
+    let Some((guild_name, owner_id)) = guild_id
+        .to_guild_cached(ctx)
+        .map(|guild| (guild.name.clone(), guild.owner_id))
+    else {
+        debug!("Failed to get cached guild '{}'.", guild_id.get());
+        return false;
+    };
+
+    let texts = texts::for_guild(ctx, Some(guild_id), &data.texts).await;
+    let description = template::render(
+        if disabled {
+            texts.destination_disabled()
+        } else {
+            texts.destination_failing()
+        },
+        &Vars::new()
+            .set("channel", ChannelId::new(channel_id).mention().to_string())
+            .set("guild", guild_name)
+            .set("failures", failures.to_string()),
+    );
+
+    let message = CreateMessage::default().embed(
+        CreateEmbed::default()
+            .color(Colors::ERROR)
+            .title(texts.destination_unusable())
+            .description(description),
+    );
+
+    let result = match alert_channel {
+        Some(alert_channel) => {
+            ChannelId::new(alert_channel)
+                .send_message(&ctx.http, message)
+                .await
+        }
+        None => owner_id.direct_message(ctx, message).await,
+    };
+
+    match result {
+        Ok(_) => true,
+        Err(err) => {
+            error!("Failed to report unusable channel '{channel_id}': {err}");
+            false
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// Allow unused fields, which are used for Debug output
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum Error {
+    Config(crate::cache::error::Error),
+    Sending(Box<serenity::Error>),
+    Serialization(serde_json::Error),
+    Deserialization(serde_json::Error),
+    Writing(std::io::Error),
+    Reading(std::io::Error),
+}
+
+impl From<serenity::Error> for Error {
+    fn from(value: serenity::Error) -> Self {
+        Self::Sending(Box::new(value))
+    }
+}
@@ -0,0 +1,770 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    env::current_dir,
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{CreateAttachment, Embed, HttpError, StatusCode},
+    builder::CreateEmbed,
+    client::Context,
+};
+use tokio::{
+    sync::Notify,
+    task,
+    time::{sleep, timeout, Instant},
+};
+use tracing::{debug, error, info, instrument, warn};
+
+use super::{error::Error, UNKNOWN_WEBHOOK_ERROR_CODE};
+use crate::{
+    data::Data,
+    event::Event,
+    utils::{
+        embed::{self, MAX_CHARACTERS_PER_MESSAGE, MAX_EMBEDS_PER_MESSAGE},
+        format::{self, Format, MAX_CONTENT_LENGTH},
+    },
+};
+
+const MAX_FILES_PER_MESSAGE: usize = 10;
+const MAX_BYTES_PER_MESSAGE: usize = 10 * 1024 * 1024;
+const REQUEST_ENTITY_TOO_LARGE_ERROR_CODE: isize = 40005;
+const BASE_RETRY_DELAY_IN_SECONDS: u64 = 2;
+const MAX_RETRY_DELAY_IN_SECONDS: u64 = 5 * 60;
+const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+const PERSIST_INTERVAL_IN_SECONDS: u64 = 5;
+const IDLE_CHECK_INTERVAL_IN_SECONDS: u64 = 10 * 60;
+
+#[instrument]
+fn get_queue_directory() -> PathBuf {
+    let mut path = PathBuf::new();
+    path.push(current_dir().expect("The current directory could not be obtained."));
+    path.push("queue");
+
+    path
+}
+
+#[instrument]
+fn get_queue_path(channel_id: u64) -> PathBuf {
+    let mut path = get_queue_directory();
+    path.push(format!("{channel_id}.json"));
+
+    path
+}
+
+#[instrument]
+fn get_file_directory() -> PathBuf {
+    let mut path = PathBuf::new();
+    path.push(current_dir().expect("The current directory could not be obtained."));
+    path.push("attachments");
+    path.push("queue");
+
+    path
+}
+
+#[instrument]
+fn get_file_path() -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut path = get_file_directory();
+    path.push(format!(
+        "{}-{}",
+        chrono::Utc::now().timestamp_micros(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+
+    path
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct QueuedFile {
+    #[serde(rename = "n")]
+    pub filename: String,
+    #[serde(rename = "p", default)]
+    path: PathBuf,
+    #[serde(rename = "s", default)]
+    size: usize,
+    // Queue files written before attachments were stored separately kept the data inline
+    #[serde(rename = "d", default, skip_serializing)]
+    data: Vec<u8>,
+}
+
+impl QueuedFile {
+    #[instrument(skip(attachment))]
+    fn new(attachment: CreateAttachment) -> Result<Self, Error> {
+        let directory = get_file_directory();
+        if !directory.exists() {
+            if let Err(err) = fs::create_dir_all(&directory) {
+                error!("Failed to create directory: {err}");
+                return Err(Error::Writing(err));
+            }
+        }
+
+        let path = get_file_path();
+        if let Err(err) = fs::write(&path, &attachment.data) {
+            error!("Failed to write queued file: {err}");
+            return Err(Error::Writing(err));
+        }
+
+        Ok(Self {
+            filename: attachment.filename,
+            path,
+            size: attachment.data.len(),
+            data: Vec::new(),
+        })
+    }
+
+    #[instrument(skip(self))]
+    fn copy(&self) -> Result<Self, Error> {
+        if !self.data.is_empty() {
+            return Ok(self.clone());
+        }
+
+        let path = get_file_path();
+        let copied =
+            fs::hard_link(&self.path, &path).or_else(|_| fs::copy(&self.path, &path).map(|_| ()));
+
+        if let Err(err) = copied {
+            error!("Failed to copy queued file: {err}");
+            return Err(Error::Writing(err));
+        }
+
+        Ok(Self {
+            path,
+            ..self.clone()
+        })
+    }
+
+    fn size(&self) -> usize {
+        self.size.max(self.data.len())
+    }
+
+    #[instrument(skip(self))]
+    pub fn load(&self) -> Option<CreateAttachment> {
+        if !self.data.is_empty() {
+            return Some(CreateAttachment::bytes(self.data.clone(), &self.filename));
+        }
+
+        match fs::read(&self.path) {
+            Ok(data) => Some(CreateAttachment::bytes(data, &self.filename)),
+            Err(err) => {
+                debug!("Failed to read queued file '{}': {err}", self.filename);
+                None
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    fn remove(&self) {
+        if self.data.is_empty() && self.path.exists() {
+            if let Err(err) = fs::remove_file(&self.path) {
+                error!("Failed to remove queued file: {err}");
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct QueuedEntry {
+    #[serde(rename = "g")]
+    pub guild_id: u64,
+    #[serde(rename = "e")]
+    pub event: Event,
+    #[serde(rename = "m")]
+    pub embeds: Vec<Embed>,
+    #[serde(rename = "c", default)]
+    pub content: Option<String>,
+    #[serde(rename = "f", default)]
+    pub files: Vec<QueuedFile>,
+}
+
+impl QueuedEntry {
+    pub fn new(
+        guild_id: u64,
+        event: Event,
+        format: Format,
+        embeds: Vec<CreateEmbed>,
+        files: Vec<CreateAttachment>,
+    ) -> Result<Self, Error> {
+        let embeds = embeds
+            .into_iter()
+            .map(|embed| {
+                serde_json::to_value(embed)
+                    .and_then(serde_json::from_value::<Embed>)
+                    .map_err(Error::Serialization)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (embeds, content, overflow) = match format {
+            Format::Embed => {
+                let (embeds, overflow) = embed::fit(embeds);
+                (embeds, None, overflow)
+            }
+            _ => {
+                let (content, overflow) = format::render(&embeds, format);
+                (Vec::new(), Some(content), overflow)
+            }
+        };
+
+        let mut queued = Vec::new();
+
+        for file in files.into_iter().chain(overflow) {
+            match QueuedFile::new(file) {
+                Ok(file) => queued.push(file),
+                Err(err) => {
+                    queued.iter().for_each(QueuedFile::remove);
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(Self {
+            guild_id,
+            event,
+            embeds,
+            content,
+            files: queued,
+        })
+    }
+
+    pub fn copy(&self) -> Result<Self, Error> {
+        let mut files = Vec::new();
+
+        for file in &self.files {
+            match file.copy() {
+                Ok(file) => files.push(file),
+                Err(err) => {
+                    files.iter().for_each(QueuedFile::remove);
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(Self {
+            files,
+            ..self.clone()
+        })
+    }
+
+    pub fn remove_files(&mut self) {
+        for file in self.files.drain(..) {
+            file.remove();
+        }
+    }
+
+    fn bytes(&self) -> usize {
+        self.files.iter().map(QueuedFile::size).sum()
+    }
+
+    fn characters(&self) -> usize {
+        match &self.content {
+            Some(content) => content.chars().count() + 1,
+            None => self.embeds.iter().map(embed::length).sum(),
+        }
+    }
+
+    fn max_characters(&self) -> usize {
+        match self.content {
+            Some(_) => MAX_CONTENT_LENGTH,
+            None => MAX_CHARACTERS_PER_MESSAGE,
+        }
+    }
+
+    fn fits_with(&self, other: &QueuedEntry) -> bool {
+        self.guild_id == other.guild_id
+            && self.event.key() == other.event.key()
+            && self.content.is_some() == other.content.is_some()
+    }
+}
+
+#[derive(Default)]
+struct Pending {
+    entries: VecDeque<QueuedEntry>,
+    persisted: bool,
+    has_changed: bool,
+}
+
+impl Pending {
+    fn batch(&self) -> Vec<QueuedEntry> {
+        let mut batch: Vec<QueuedEntry> = Vec::new();
+        let (mut embeds, mut files, mut bytes, mut characters) = (0, 0, 0, 0);
+
+        for entry in &self.entries {
+            if let Some(first) = batch.first() {
+                if !first.fits_with(entry)
+                    || embeds + entry.embeds.len() > MAX_EMBEDS_PER_MESSAGE
+                    || files + entry.files.len() > MAX_FILES_PER_MESSAGE
+                    || bytes + entry.bytes() > MAX_BYTES_PER_MESSAGE
+                    || characters + entry.characters() > entry.max_characters()
+                {
+                    break;
+                }
+            }
+
+            embeds += entry.embeds.len();
+            files += entry.files.len();
+            bytes += entry.bytes();
+            characters += entry.characters();
+            batch.push(entry.clone());
+        }
+
+        batch
+    }
+}
+
+#[derive(Default)]
+struct ChannelQueue {
+    guild_id: AtomicU64,
+    pending: Mutex<Pending>,
+    notify: Notify,
+    failures: AtomicU32,
+}
+
+pub struct DeliveryQueue {
+    channels: Mutex<HashMap<u64, Arc<ChannelQueue>>>,
+    restored: AtomicBool,
+}
+
+impl DeliveryQueue {
+    #[instrument]
+    pub fn new() -> Self {
+        Self {
+            channels: Mutex::new(HashMap::new()),
+            restored: AtomicBool::new(false),
+        }
+    }
+
+    #[instrument(skip(self, ctx, entries))]
+    pub fn push(&self, ctx: &Context, channel_id: u64, entries: Vec<QueuedEntry>) {
+        self.enqueue(ctx, channel_id, entries, false);
+    }
+
+    #[instrument(skip(self, ctx))]
+    pub async fn fail(&self, ctx: &Context, guild_id: u64, channel_id: u64) {
+        let queue = {
+            let mut channels = self
+                .channels
+                .lock()
+                .expect("Queue lock should never be poisoned.");
+            Self::channel(&mut channels, ctx, guild_id, channel_id)
+        };
+
+        Self::failed(ctx, guild_id, channel_id, &queue).await;
+    }
+
+    fn channel(
+        channels: &mut HashMap<u64, Arc<ChannelQueue>>,
+        ctx: &Context,
+        guild_id: u64,
+        channel_id: u64,
+    ) -> Arc<ChannelQueue> {
+        let queue = channels.entry(channel_id).or_insert_with(|| {
+            let queue = Arc::new(ChannelQueue::default());
+            tokio::spawn(Self::work(ctx.clone(), channel_id, queue.clone()));
+            queue
+        });
+
+        queue.guild_id.store(guild_id, Ordering::SeqCst);
+
+        queue.clone()
+    }
+
+    fn enqueue(&self, ctx: &Context, channel_id: u64, entries: Vec<QueuedEntry>, stored: bool) {
+        let Some(guild_id) = entries.first().map(|entry| entry.guild_id) else {
+            return;
+        };
+
+        // The map stays locked while pushing so an idle worker can't retire in between
+        let mut channels = self
+            .channels
+            .lock()
+            .expect("Queue lock should never be poisoned.");
+        let queue = Self::channel(&mut channels, ctx, guild_id, channel_id);
+
+        {
+            let mut pending = queue
+                .pending
+                .lock()
+                .expect("Queue lock should never be poisoned.");
+
+            pending.entries.extend(entries);
+            pending.persisted |= stored;
+            pending.has_changed = true;
+        }
+
+        queue.notify.notify_one();
+    }
+
+    #[instrument(skip(ctx, queue))]
+    async fn work(ctx: Context, channel_id: u64, queue: Arc<ChannelQueue>) {
+        let mut attempt = 0;
+        let mut persisted_at = Instant::now();
+
+        loop {
+            let batch = queue
+                .pending
+                .lock()
+                .expect("Queue lock should never be poisoned.")
+                .batch();
+
+            if batch.is_empty() {
+                Self::persist(channel_id, &queue).await;
+
+                let idle = timeout(
+                    Duration::from_secs(IDLE_CHECK_INTERVAL_IN_SECONDS),
+                    queue.notify.notified(),
+                )
+                .await
+                .is_err();
+
+                if idle && Self::retire(&ctx, channel_id, &queue).await {
+                    debug!("Stopped the delivery worker of channel '{channel_id}'.");
+                    return;
+                }
+
+                continue;
+            }
+
+            // Undelivered entries are written at most once per interval instead of on every push
+            if persisted_at.elapsed() >= Duration::from_secs(PERSIST_INTERVAL_IN_SECONDS) {
+                Self::persist(channel_id, &queue).await;
+                persisted_at = Instant::now();
+            }
+
+            match super::transmit(&ctx, channel_id, &batch).await {
+                Ok(()) => {
+                    attempt = 0;
+                    queue.failures.store(0, Ordering::SeqCst);
+                    Self::complete(&queue, batch.len());
+                }
+                Err(err) if is_transient(&err) => {
+                    attempt += 1;
+                    let delay = retry_delay(attempt);
+                    warn!(
+                        "Failed to deliver to channel '{channel_id}', retrying in {} seconds: {err:?}",
+                        delay.as_secs()
+                    );
+
+                    Self::persist(channel_id, &queue).await;
+                    persisted_at = Instant::now();
+
+                    sleep(delay).await;
+                }
+                Err(err) if is_too_large(&err) && Self::strip_files(&queue, batch.len()) => {
+                    warn!(
+                        "Upload to channel '{channel_id}' was too large, retrying without files: {err:?}"
+                    );
+                }
+                Err(err) => {
+                    attempt = 0;
+                    error!(
+                        "Failed to deliver to channel '{channel_id}', dropping entries: {err:?}"
+                    );
+                    Self::complete(&queue, batch.len());
+                    Self::failed(&ctx, batch[0].guild_id, channel_id, &queue).await;
+                }
+            }
+        }
+    }
+
+    // Workers of channels that are no longer log destinations stop once their queue is empty
+    #[instrument(skip(ctx, queue))]
+    async fn retire(ctx: &Context, channel_id: u64, queue: &Arc<ChannelQueue>) -> bool {
+        let guild_id = queue.guild_id.load(Ordering::SeqCst);
+
+        let data = ctx.data.read().await;
+        let data = data.get::<Data>().expect("Data should never be none.");
+
+        match data.cache.get_or_insert(guild_id).await {
+            Ok(config) if config.is_destination(channel_id) => return false,
+            Ok(_) => {}
+            Err(err) => {
+                error!("Failed to get GuildConfig: {err:?}");
+                return false;
+            }
+        }
+
+        let mut channels = data
+            .queue
+            .channels
+            .lock()
+            .expect("Queue lock should never be poisoned.");
+
+        let is_empty = queue
+            .pending
+            .lock()
+            .expect("Queue lock should never be poisoned.")
+            .entries
+            .is_empty();
+
+        if !is_empty
+            || !channels
+                .get(&channel_id)
+                .is_some_and(|current| Arc::ptr_eq(current, queue))
+        {
+            return false;
+        }
+
+        channels.remove(&channel_id);
+
+        true
+    }
+
+    async fn failed(ctx: &Context, guild_id: u64, channel_id: u64, queue: &ChannelQueue) {
+        let failures = queue.failures.fetch_add(1, Ordering::SeqCst) + 1;
+
+        if failures >= MAX_CONSECUTIVE_FAILURES
+            && super::report_unusable(ctx, guild_id, channel_id, failures).await
+        {
+            queue.failures.store(0, Ordering::SeqCst);
+        }
+    }
+
+    fn complete(queue: &ChannelQueue, count: usize) {
+        let mut pending = queue
+            .pending
+            .lock()
+            .expect("Queue lock should never be poisoned.");
+
+        for mut entry in pending.entries.drain(..count) {
+            entry.remove_files();
+        }
+
+        if pending.persisted {
+            pending.has_changed = true;
+        }
+    }
+
+    fn strip_files(queue: &ChannelQueue, count: usize) -> bool {
+        let mut pending = queue
+            .pending
+            .lock()
+            .expect("Queue lock should never be poisoned.");
+
+        let mut stripped = false;
+
+        for entry in pending.entries.iter_mut().take(count) {
+            stripped |= !entry.files.is_empty();
+            entry.remove_files();
+        }
+
+        if stripped && pending.persisted {
+            pending.has_changed = true;
+        }
+
+        stripped
+    }
+
+    #[instrument(skip(queue))]
+    async fn persist(channel_id: u64, queue: &ChannelQueue) {
+        let entries = {
+            let mut pending = queue
+                .pending
+                .lock()
+                .expect("Queue lock should never be poisoned.");
+
+            if !pending.has_changed {
+                return;
+            }
+
+            pending.has_changed = false;
+
+            // Entries delivered before they were ever written don't need a file
+            if pending.entries.is_empty() && !pending.persisted {
+                return;
+            }
+
+            pending.entries.iter().cloned().collect::<Vec<_>>()
+        };
+
+        let is_empty = entries.is_empty();
+        let saved = task::spawn_blocking(move || Self::save(channel_id, &entries))
+            .await
+            .is_ok_and(|result| result.is_ok());
+
+        let mut pending = queue
+            .pending
+            .lock()
+            .expect("Queue lock should never be poisoned.");
+
+        if saved {
+            pending.persisted = !is_empty;
+        } else {
+            pending.has_changed = true;
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub fn persist_all(&self) {
+        info!("Persisting undelivered log entries...");
+        let channels = self
+            .channels
+            .lock()
+            .expect("Queue lock should never be poisoned.");
+
+        for (channel_id, queue) in channels.iter() {
+            let mut pending = queue
+                .pending
+                .lock()
+                .expect("Queue lock should never be poisoned.");
+
+            if !pending.has_changed {
+                continue;
+            }
+
+            let entries = pending.entries.iter().cloned().collect::<Vec<_>>();
+
+            if Self::save(*channel_id, &entries).is_ok() {
+                pending.persisted = !entries.is_empty();
+                pending.has_changed = false;
+            }
+        }
+    }
+
+    #[instrument(skip(self, ctx))]
+    pub fn restore(&self, ctx: &Context) {
+        if self.restored.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let directory = get_queue_directory();
+        let entries = match fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(err) => {
+                debug!("Failed to read queue directory: {err}");
+                return;
+            }
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(channel_id) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            else {
+                continue;
+            };
+
+            let Ok(entries) = Self::load(channel_id) else {
+                continue;
+            };
+
+            info!(
+                "Restoring {} undelivered log entries for channel '{channel_id}'.",
+                entries.len()
+            );
+            self.enqueue(ctx, channel_id, entries, true);
+        }
+    }
+
+    #[instrument]
+    fn load(channel_id: u64) -> Result<Vec<QueuedEntry>, Error> {
+        let path = get_queue_path(channel_id);
+
+        let raw_data = match fs::read_to_string(&path) {
+            Ok(raw_data) => raw_data,
+            Err(err) => {
+                error!("Failed to read queue file: {err}");
+                return Err(Error::Reading(err));
+            }
+        };
+
+        match serde_json::from_str(&raw_data) {
+            Ok(entries) => Ok(entries),
+            Err(err) => {
+                error!("Failed to deserialize queued entries from JSON: {err}");
+                Err(Error::Deserialization(err))
+            }
+        }
+    }
+
+    #[instrument(skip(entries))]
+    fn save(channel_id: u64, entries: &[QueuedEntry]) -> Result<(), Error> {
+        let path = get_queue_path(channel_id);
+
+        if entries.is_empty() {
+            if path.exists() {
+                if let Err(err) = fs::remove_file(&path) {
+                    error!("Failed to remove queue file: {err}");
+                    return Err(Error::Writing(err));
+                }
+            }
+
+            return Ok(());
+        }
+
+        debug!("Saving queued entries...");
+        let directory = get_queue_directory();
+        if !directory.exists() {
+            if let Err(err) = fs::create_dir(&directory) {
+                error!("Failed to create directory: {err}");
+                return Err(Error::Writing(err));
+            };
+        }
+
+        let json = match serde_json::to_string(entries) {
+            Ok(json) => json,
+            Err(err) => {
+                error!("Failed to serialize queued entries to JSON: {err}");
+                return Err(Error::Serialization(err));
+            }
+        };
+
+        match fs::write(path, json) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                error!("Failed to write queue file: {err}");
+                Err(Error::Writing(err))
+            }
+        }
+    }
+}
+
+impl Default for DeliveryQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_transient(err: &Error) -> bool {
+    match err {
+        Error::Config(_) => true,
+        Error::Sending(err) => match err.as_ref() {
+            serenity::Error::Http(HttpError::UnsuccessfulRequest(response)) => {
+                response.status_code == StatusCode::TOO_MANY_REQUESTS
+                    || response.status_code.is_server_error()
+                    || response.error.code == UNKNOWN_WEBHOOK_ERROR_CODE
+            }
+            serenity::Error::Http(HttpError::Request(_)) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn is_too_large(err: &Error) -> bool {
+    match err {
+        Error::Sending(err) => match err.as_ref() {
+            serenity::Error::Http(HttpError::UnsuccessfulRequest(response)) => {
+                response.status_code == StatusCode::PAYLOAD_TOO_LARGE
+                    || response.error.code == REQUEST_ENTITY_TOO_LARGE_ERROR_CODE
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn retry_delay(attempt: u32) -> Duration {
+    let seconds = BASE_RETRY_DELAY_IN_SECONDS * 2u64.pow(attempt.saturating_sub(1).min(16));
+
+    Duration::from_secs(seconds.min(MAX_RETRY_DELAY_IN_SECONDS))
+}
@@ -1,27 +1,291 @@
+use serde::{Deserialize, Serialize};
+
+mod ban;
+pub mod channel_create;
+pub mod channel_delete;
+pub mod channel_update;
+pub mod guild_ban_addition;
+pub mod guild_ban_removal;
 pub mod guild_member_addition;
 pub mod guild_member_removal;
+pub mod guild_member_update;
+pub mod guild_role_create;
+pub mod guild_role_delete;
+pub mod guild_role_update;
 pub mod message_delete;
+pub mod message_delete_bulk;
 pub mod message_update;
+pub mod thread_create;
+pub mod thread_delete;
+pub mod thread_update;
+pub mod voice_state_update;
 
-#[derive(poise::ChoiceParameter)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, poise::ChoiceParameter)]
 pub enum Event {
     #[name = "Message Delete"]
     MessageDelete,
     #[name = "Message Update"]
     MessageUpdate,
+    #[name = "Message Bulk Delete"]
+    MessageDeleteBulk,
     #[name = "User Join"]
     GuildMemberAddition,
     #[name = "User Left"]
     GuildMemberRemoval,
+    #[name = "User Update"]
+    GuildMemberUpdate,
+    #[name = "User Ban"]
+    GuildBanAddition,
+    #[name = "User Unban"]
+    GuildBanRemoval,
+    #[name = "Voice Update"]
+    VoiceStateUpdate,
+    #[name = "Channel Create"]
+    ChannelCreate,
+    #[name = "Channel Delete"]
+    ChannelDelete,
+    #[name = "Channel Update"]
+    ChannelUpdate,
+    #[name = "Thread Create"]
+    ThreadCreate,
+    #[name = "Thread Delete"]
+    ThreadDelete,
+    #[name = "Thread Update"]
+    ThreadUpdate,
+    #[name = "Role Create"]
+    GuildRoleCreate,
+    #[name = "Role Delete"]
+    GuildRoleDelete,
+    #[name = "Role Update"]
+    GuildRoleUpdate,
 }
 
 impl Event {
-    pub fn key(&self) -> &str {
+    pub const ALL: [Event; 18] = [
+        Self::MessageDelete,
+        Self::MessageUpdate,
+        Self::MessageDeleteBulk,
+        Self::GuildMemberAddition,
+        Self::GuildMemberRemoval,
+        Self::GuildMemberUpdate,
+        Self::GuildBanAddition,
+        Self::GuildBanRemoval,
+        Self::VoiceStateUpdate,
+        Self::ChannelCreate,
+        Self::ChannelDelete,
+        Self::ChannelUpdate,
+        Self::ThreadCreate,
+        Self::ThreadDelete,
+        Self::ThreadUpdate,
+        Self::GuildRoleCreate,
+        Self::GuildRoleDelete,
+        Self::GuildRoleUpdate,
+    ];
+
+    pub const MESSAGE_EVENTS: [Event; 3] = [
+        Self::MessageDelete,
+        Self::MessageUpdate,
+        Self::MessageDeleteBulk,
+    ];
+
+    pub fn from_key(key: &str) -> Option<Event> {
+        Self::ALL.into_iter().find(|event| event.key() == key)
+    }
+
+    pub fn key(&self) -> &'static str {
         match self {
             Self::MessageDelete => "MD",
             Self::MessageUpdate => "MU",
+            Self::MessageDeleteBulk => "MB",
             Self::GuildMemberAddition => "UJ",
             Self::GuildMemberRemoval => "UL",
+            Self::GuildMemberUpdate => "UU",
+            Self::GuildBanAddition => "UB",
+            Self::GuildBanRemoval => "UN",
+            Self::VoiceStateUpdate => "VS",
+            Self::ChannelCreate => "CC",
+            Self::ChannelDelete => "CD",
+            Self::ChannelUpdate => "CU",
+            Self::ThreadCreate => "TC",
+            Self::ThreadDelete => "TD",
+            Self::ThreadUpdate => "TU",
+            Self::GuildRoleCreate => "RC",
+            Self::GuildRoleDelete => "RD",
+            Self::GuildRoleUpdate => "RU",
+        }
+    }
+
+    pub fn fields(&self) -> &'static [&'static str] {
+        match self {
+            Self::MessageDelete => &["content", "attachments", "stickers", "embeds", "date", "id"],
+            Self::MessageUpdate => &[
+                "now",
+                "previous",
+                "changes",
+                "removed_attachments",
+                "date",
+                "id",
+            ],
+            Self::MessageDeleteBulk => &["count", "time_range", "date"],
+            Self::GuildMemberAddition => &["id", "account_creation"],
+            Self::GuildMemberRemoval => {
+                &["id", "account_creation", "moderator", "reason", "joined"]
+            }
+            Self::GuildMemberUpdate => &[
+                "nick",
+                "roles_added",
+                "roles_removed",
+                "roles",
+                "timeout",
+                "avatar",
+                "pending",
+                "id",
+            ],
+            Self::GuildBanAddition | Self::GuildBanRemoval => &["id", "moderator", "reason"],
+            Self::VoiceStateUpdate => &["moderator", "id"],
+            Self::ChannelCreate | Self::ChannelDelete => &["kind", "category", "id"],
+            Self::ChannelUpdate => &[
+                "name",
+                "topic",
+                "nsfw",
+                "slowmode",
+                "category",
+                "overwrite",
+                "id",
+            ],
+            Self::ThreadCreate => &["kind", "channel", "owner", "id"],
+            Self::ThreadDelete => &["kind", "channel", "id"],
+            Self::ThreadUpdate => &[
+                "name",
+                "archived",
+                "locked",
+                "auto_archive",
+                "slowmode",
+                "id",
+            ],
+            Self::GuildRoleCreate => &["color", "hoist", "mentionable", "permissions", "id"],
+            Self::GuildRoleDelete => &["color", "id"],
+            Self::GuildRoleUpdate => &[
+                "name",
+                "color",
+                "hoist",
+                "mentionable",
+                "position",
+                "permissions_added",
+                "permissions_removed",
+                "id",
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn source(event: Event) -> &'static str {
+        match event {
+            Event::MessageDelete => include_str!("event/message_delete.rs"),
+            Event::MessageUpdate => include_str!("event/message_update.rs"),
+            Event::MessageDeleteBulk => include_str!("event/message_delete_bulk.rs"),
+            Event::GuildMemberAddition => include_str!("event/guild_member_addition.rs"),
+            Event::GuildMemberRemoval => include_str!("event/guild_member_removal.rs"),
+            Event::GuildMemberUpdate => include_str!("event/guild_member_update.rs"),
+            Event::GuildBanAddition | Event::GuildBanRemoval => include_str!("event/ban.rs"),
+            Event::VoiceStateUpdate => include_str!("event/voice_state_update.rs"),
+            Event::ChannelCreate => include_str!("event/channel_create.rs"),
+            Event::ChannelDelete => include_str!("event/channel_delete.rs"),
+            Event::ChannelUpdate => include_str!("event/channel_update.rs"),
+            Event::ThreadCreate => include_str!("event/thread_create.rs"),
+            Event::ThreadDelete => include_str!("event/thread_delete.rs"),
+            Event::ThreadUpdate => include_str!("event/thread_update.rs"),
+            Event::GuildRoleCreate => include_str!("event/guild_role_create.rs"),
+            Event::GuildRoleDelete => include_str!("event/guild_role_delete.rs"),
+            Event::GuildRoleUpdate => include_str!("event/guild_role_update.rs"),
+        }
+    }
+
+    fn identifier_end(text: &str) -> &str {
+        let start = text
+            .rfind(|character: char| !character.is_ascii_alphanumeric() && character != '_')
+            .map_or(0, |index| index + 1);
+
+        &text[start..]
+    }
+
+    // Field ids are the first argument of `.field(..)` or of a helper taking `id: &'static str`,
+    // or the first element of a `(id, name, value, inline)` tuple.
+    fn emitted_fields(source: &str) -> BTreeSet<String> {
+        let mut code = String::new();
+        let mut in_variables = false;
+
+        for line in source.lines() {
+            if line.contains("Variables = &[") || line.contains("&[&str] = &[") {
+                in_variables = true;
+            }
+
+            if !in_variables {
+                code.push_str(line);
+                code.push('\n');
+            }
+
+            if in_variables && line.trim_end().ends_with("];") {
+                in_variables = false;
+            }
+        }
+
+        let mut helpers = vec!["field"];
+
+        for (index, _) in source.match_indices("id: &'static str") {
+            let before = source[..index].trim_end_matches([' ', '=', '|', '(']);
+            helpers.push(identifier_end(before));
+        }
+
+        let mut fields = BTreeSet::new();
+
+        for (index, _) in code.match_indices('"') {
+            let rest = &code[index + 1..];
+            let Some(end) = rest.find('"') else {
+                continue;
+            };
+            let literal = &rest[..end];
+
+            if literal.is_empty()
+                || !literal
+                    .chars()
+                    .all(|character| character.is_ascii_lowercase() || character == '_')
+            {
+                continue;
+            }
+
+            let Some(before) = code[..index].trim_end().strip_suffix('(') else {
+                continue;
+            };
+            let callee = identifier_end(before);
+
+            if helpers.contains(&callee) || callee.is_empty() {
+                fields.insert(literal.to_string());
+            }
+        }
+
+        fields
+    }
+
+    #[test]
+    fn emitted_fields_are_listed() {
+        for event in Event::ALL {
+            let emitted = emitted_fields(source(event));
+
+            assert!(!emitted.is_empty(), "{event:?} emits no fields");
+
+            for field in emitted {
+                assert!(
+                    event.fields().contains(&field.as_str()),
+                    "{event:?} emits '{field}' but doesn't list it"
+                );
+            }
         }
     }
 }
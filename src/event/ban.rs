@@ -0,0 +1,122 @@
+use serenity::{
+    all::{GuildId, Mentionable, User},
+    client::Context,
+    model::guild::audit_log::Action,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    audit, channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Author, Subject},
+    layout::LogEmbed,
+    template::{self, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub struct Texts<'a> {
+    pub title: &'a str,
+    pub id: &'a str,
+    pub moderator: &'a str,
+    pub reason: &'a str,
+    pub unknown: &'a str,
+    pub no_reason: &'a str,
+}
+
+// Shared by ban additions and removals, which only differ in their event, audit action and texts
+#[instrument(skip(ctx, guild_id, user, texts, select))]
+pub async fn ban_event(
+    ctx: Context,
+    guild_id: GuildId,
+    user: User,
+    event: Event,
+    action: Action,
+    texts: &GlobalTexts,
+    select: fn(&GlobalTexts) -> Texts<'_>,
+) {
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, event).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        event,
+        destinations,
+        Subject {
+            user: Some(user.id),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+    let texts = select(&texts);
+
+    if ignore::is_author_ignored(
+        &ctx,
+        guild_id,
+        event,
+        Author {
+            id: user.id,
+            bot: user.bot,
+            webhook_id: None,
+        },
+    )
+    .await
+    {
+        debug!("The user was an ignored bot.");
+        return;
+    }
+
+    let entry = audit::find_by_target(&ctx, guild_id, action, user.id.get()).await;
+
+    let (moderator, reason) = match entry {
+        Some(entry) => (
+            entry.user_id.mention().to_string(),
+            entry.reason.unwrap_or(texts.no_reason.to_string()),
+        ),
+        None => (texts.unknown.to_string(), texts.unknown.to_string()),
+    };
+
+    let mut embed = LogEmbed::default()
+        .title(template::render(
+            texts.title,
+            &Vars::new().user("user", user.global_name.as_ref().unwrap_or(&user.name)),
+        ))
+        .description(user.mention().to_string())
+        .color(Colors::PRIMARY)
+        .field(
+            "id",
+            texts.id,
+            format!("@{} ({})", user.name, user.id),
+            true,
+        )
+        .field("moderator", texts.moderator, moderator, true)
+        .field("reason", texts.reason, reason, false);
+
+    if let Some(url) = user.avatar_url() {
+        embed = embed.thumbnail(url);
+    }
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        event,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
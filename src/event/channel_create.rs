@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{GuildChannel, Mentionable},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[("title", &["channel_name"])];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    kind: String,
+    category: String,
+    none: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("Channel #%channel_name% was created"),
+            kind: String::from("Type"),
+            category: String::from("Category"),
+            none: String::from("None"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, created, texts))]
+pub async fn channel_create_event(ctx: Context, created: GuildChannel, texts: &GlobalTexts) {
+    let guild_id = created.guild_id;
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::ChannelCreate).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::ChannelCreate,
+        destinations,
+        Subject {
+            channels: [Some(created.id), created.parent_id]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    let embed = LogEmbed::default()
+        .title(template::render(
+            &texts.channel_create.title,
+            &Vars::new().user("channel_name", &created.name),
+        ))
+        .description(created.mention().to_string())
+        .color(Colors::PRIMARY)
+        .field(
+            "kind",
+            &texts.channel_create.kind,
+            format!("`{}`", created.kind.name()),
+            true,
+        )
+        .field(
+            "category",
+            &texts.channel_create.category,
+            created
+                .parent_id
+                .map(|id| id.mention().to_string())
+                .unwrap_or(texts.channel_create.none.clone()),
+            true,
+        )
+        .field(
+            "id",
+            &texts.channel_create.id,
+            format!("`{}`", created.id.get()),
+            true,
+        );
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::ChannelCreate,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{GuildChannel, Mentionable},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[("title", &["channel_name"])];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    kind: String,
+    category: String,
+    none: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("Channel #%channel_name% was deleted"),
+            kind: String::from("Type"),
+            category: String::from("Category"),
+            none: String::from("None"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, deleted, texts))]
+pub async fn channel_delete_event(ctx: Context, deleted: GuildChannel, texts: &GlobalTexts) {
+    let guild_id = deleted.guild_id;
+
+    channel::forget(&ctx, guild_id, deleted.id).await;
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::ChannelDelete).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::ChannelDelete,
+        destinations,
+        Subject {
+            channels: [Some(deleted.id), deleted.parent_id]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    let embed = LogEmbed::default()
+        .title(template::render(
+            &texts.channel_delete.title,
+            &Vars::new().user("channel_name", &deleted.name),
+        ))
+        .color(Colors::PRIMARY)
+        .field(
+            "kind",
+            &texts.channel_delete.kind,
+            format!("`{}`", deleted.kind.name()),
+            true,
+        )
+        .field(
+            "category",
+            &texts.channel_delete.category,
+            deleted
+                .parent_id
+                .map(|id| id.mention().to_string())
+                .unwrap_or(texts.channel_delete.none.clone()),
+            true,
+        )
+        .field(
+            "id",
+            &texts.channel_delete.id,
+            format!("`{}`", deleted.id.get()),
+            true,
+        );
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::ChannelDelete,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
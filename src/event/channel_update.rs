@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{ChannelId, GuildChannel, Mentionable},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+    utils::permissions::{self, OverwriteChangeKind},
+};
+
+pub const VARIABLES: Variables = &[
+    ("title", &["channel_name"]),
+    ("change_body", &["old", "new"]),
+    ("seconds", &["seconds"]),
+    ("allowed", &["permissions"]),
+    ("denied", &["permissions"]),
+    ("inherited", &["permissions"]),
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    name: String,
+    topic: String,
+    nsfw: String,
+    slowmode: String,
+    category: String,
+    change_body: String,
+    none: String,
+    enabled: String,
+    disabled: String,
+    seconds: String,
+    overwrite_added: String,
+    overwrite_removed: String,
+    overwrite_updated: String,
+    allowed: String,
+    denied: String,
+    inherited: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("Channel #%channel_name% was updated"),
+            name: String::from("Name"),
+            topic: String::from("Topic"),
+            nsfw: String::from("NSFW"),
+            slowmode: String::from("Slowmode"),
+            category: String::from("Category"),
+            change_body: String::from("%old% → %new%"),
+            none: String::from("None"),
+            enabled: String::from("Enabled"),
+            disabled: String::from("Disabled"),
+            seconds: String::from("%seconds%s"),
+            overwrite_added: String::from("Permission Overwrite Added"),
+            overwrite_removed: String::from("Permission Overwrite Removed"),
+            overwrite_updated: String::from("Permission Overwrite Updated"),
+            allowed: String::from("✅ %permissions%"),
+            denied: String::from("❌ %permissions%"),
+            inherited: String::from("➖ %permissions%"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, old, new, texts))]
+pub async fn channel_update_event(
+    ctx: Context,
+    old: Option<GuildChannel>,
+    new: GuildChannel,
+    texts: &GlobalTexts,
+) {
+    let guild_id = new.guild_id;
+
+    let Some(old) = old else {
+        debug!("Old channel data was not present.");
+        return;
+    };
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::ChannelUpdate).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::ChannelUpdate,
+        destinations,
+        Subject {
+            channels: [Some(new.id), new.parent_id, old.parent_id]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+    let texts = &texts.channel_update;
+    let mut fields = Vec::new();
+
+    let change = |old: &str, new: &str| {
+        template::render(
+            &texts.change_body,
+            &Vars::new().set("old", old).set("new", new),
+        )
+    };
+
+    if old.name != new.name {
+        fields.push((
+            "name",
+            texts.name.clone(),
+            change(&template::escape(&old.name), &template::escape(&new.name)),
+            false,
+        ));
+    }
+
+    if old.topic != new.topic {
+        fields.push((
+            "topic",
+            texts.topic.clone(),
+            change(
+                &old.topic
+                    .as_deref()
+                    .map_or(texts.none.clone(), template::escape),
+                &new.topic
+                    .as_deref()
+                    .map_or(texts.none.clone(), template::escape),
+            ),
+            false,
+        ));
+    }
+
+    if old.nsfw != new.nsfw {
+        let state = |nsfw: bool| {
+            if nsfw {
+                &texts.enabled
+            } else {
+                &texts.disabled
+            }
+        };
+
+        fields.push((
+            "nsfw",
+            texts.nsfw.clone(),
+            change(state(old.nsfw), state(new.nsfw)),
+            true,
+        ));
+    }
+
+    if old.rate_limit_per_user != new.rate_limit_per_user {
+        let seconds = |seconds: Option<u16>| match seconds {
+            Some(seconds) if seconds > 0 => {
+                template::render(&texts.seconds, &Vars::new().set("seconds", seconds))
+            }
+            _ => texts.disabled.clone(),
+        };
+
+        fields.push((
+            "slowmode",
+            texts.slowmode.clone(),
+            change(
+                &seconds(old.rate_limit_per_user),
+                &seconds(new.rate_limit_per_user),
+            ),
+            true,
+        ));
+    }
+
+    if old.parent_id != new.parent_id {
+        let category = |id: Option<ChannelId>| {
+            id.map(|id| id.mention().to_string())
+                .unwrap_or(texts.none.clone())
+        };
+
+        fields.push((
+            "category",
+            texts.category.clone(),
+            change(&category(old.parent_id), &category(new.parent_id)),
+            true,
+        ));
+    }
+
+    for overwrite in
+        permissions::diff_overwrites(&old.permission_overwrites, &new.permission_overwrites)
+    {
+        let name = match overwrite.kind {
+            OverwriteChangeKind::Added => &texts.overwrite_added,
+            OverwriteChangeKind::Removed => &texts.overwrite_removed,
+            OverwriteChangeKind::Updated => &texts.overwrite_updated,
+        };
+
+        let mut lines = vec![permissions::mention_target(&overwrite.target, guild_id)];
+
+        for (text, permissions) in [
+            (&texts.allowed, overwrite.allowed),
+            (&texts.denied, overwrite.denied),
+            (&texts.inherited, overwrite.inherited),
+        ] {
+            if !permissions.is_empty() {
+                lines.push(template::render(
+                    text,
+                    &Vars::new().set("permissions", permissions::names(permissions)),
+                ));
+            }
+        }
+
+        fields.push(("overwrite", name.clone(), lines.join("\n"), false));
+    }
+
+    if fields.is_empty() {
+        debug!("No logged channel properties changed.");
+        return;
+    }
+
+    fields.push(("id", texts.id.clone(), format!("`{}`", new.id.get()), false));
+
+    let embed = LogEmbed::default()
+        .title(template::render(
+            &texts.title,
+            &Vars::new().user("channel_name", &new.name),
+        ))
+        .description(new.mention().to_string())
+        .color(Colors::PRIMARY)
+        .fields(fields);
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::ChannelUpdate,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
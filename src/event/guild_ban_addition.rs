@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{GuildId, User},
+    client::Context,
+    model::guild::audit_log::{Action, MemberAction},
+};
+use tracing::instrument;
+
+use super::ban;
+use crate::{event::Event, template::Variables, texts::Texts as GlobalTexts};
+
+pub const VARIABLES: Variables = &[("title", &["user"])];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    id: String,
+    moderator: String,
+    reason: String,
+    unknown: String,
+    no_reason: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("%user% was banned"),
+            id: String::from("ID"),
+            moderator: String::from("Moderator"),
+            reason: String::from("Reason"),
+            unknown: String::from("Unknown"),
+            no_reason: String::from("No reason was provided."),
+        }
+    }
+}
+
+fn ban_texts(texts: &GlobalTexts) -> ban::Texts<'_> {
+    let texts = &texts.guild_ban_addition;
+
+    ban::Texts {
+        title: &texts.title,
+        id: &texts.id,
+        moderator: &texts.moderator,
+        reason: &texts.reason,
+        unknown: &texts.unknown,
+        no_reason: &texts.no_reason,
+    }
+}
+
+#[instrument(skip(ctx, guild_id, user, texts))]
+pub async fn guild_ban_addition_event(
+    ctx: Context,
+    guild_id: GuildId,
+    user: User,
+    texts: &GlobalTexts,
+) {
+    ban::ban_event(
+        ctx,
+        guild_id,
+        user,
+        Event::GuildBanAddition,
+        Action::Member(MemberAction::BanAdd),
+        texts,
+        ban_texts,
+    )
+    .await
+}
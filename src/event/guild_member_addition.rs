@@ -1,12 +1,22 @@
 use serde::{Deserialize, Serialize};
 use serenity::{
-    all::{ChannelId, CreateEmbedFooter, Member, Mentionable},
-    builder::{CreateEmbed, CreateMessage},
+    all::{Member, Mentionable},
     client::Context,
 };
-use tracing::{debug, error, instrument};
+use tracing::{debug, instrument};
 
-use crate::{channel, colors::Colors, event::Event, texts::Texts as GlobalTexts};
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Author, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[("title", &["user"]), ("member_count", &["count"])];
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -32,7 +42,8 @@ impl Default for Texts {
 pub async fn guild_member_addition_event(ctx: Context, member: Member, texts: &GlobalTexts) {
     let guild_id = member.guild_id;
 
-    let Ok(Some(config_id)) = channel::get(&ctx, guild_id, Event::GuildMemberAddition).await else {
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::GuildMemberAddition).await
+    else {
         debug!(
             "ChannelId of guild '{}' was either None or Err.",
             guild_id.get()
@@ -40,66 +51,93 @@ pub async fn guild_member_addition_event(ctx: Context, member: Member, texts: &G
         return;
     };
 
-    if member.user.bot {
-        debug!("The user who joined was a bot.");
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::GuildMemberAddition,
+        destinations,
+        Subject {
+            user: Some(member.user.id),
+            roles: member.roles.clone(),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    if ignore::is_author_ignored(
+        &ctx,
+        guild_id,
+        Event::GuildMemberAddition,
+        Author {
+            id: member.user.id,
+            bot: member.user.bot,
+            webhook_id: None,
+        },
+    )
+    .await
+    {
+        debug!("The user who joined was an ignored bot.");
         return;
     }
 
-    let (member_count, channel) = {
+    let member_count = {
         let Some(guild) = guild_id.to_guild_cached(&ctx) else {
             debug!("Failed to get cached guild '{}'.", guild_id.get());
             return;
         };
 
-        let Some(channel) = guild.channels.get(&ChannelId::from(config_id)) else {
-            debug!(
-                "Failed to get guild '{}' channel '{config_id}'.",
-                guild_id.get()
-            );
-            return;
-        };
-
-        (guild.member_count, channel.clone())
+        guild.member_count
     };
 
-    let mut embed = CreateEmbed::default()
-        .title(
-            texts.guild_member_addition.title.replace(
-                "%user%",
+    let mut embed = LogEmbed::default()
+        .title(template::render(
+            &texts.guild_member_addition.title,
+            &Vars::new().user(
+                "user",
                 member
                     .user
                     .global_name
                     .as_ref()
                     .unwrap_or(&member.user.name),
             ),
-        )
+        ))
         .description(member.user.mention().to_string())
         .color(Colors::PRIMARY)
         .field(
+            "id",
             &texts.guild_member_addition.id,
             format!("@{} ({})", member.user.name, member.user.id),
             true,
         )
         .field(
+            "account_creation",
             &texts.guild_member_addition.account_creation,
             format!("<t:{}>", member.user.created_at().timestamp()),
             true,
         )
-        .footer(CreateEmbedFooter::new(
-            texts
-                .guild_member_addition
-                .member_count
-                .replace("%count%", &member_count.to_string()),
+        .footer(template::render(
+            &texts.guild_member_addition.member_count,
+            &Vars::new().set("count", member_count),
         ));
 
     if let Some(url) = member.user.avatar_url() {
         embed = embed.thumbnail(url);
     }
 
-    if let Err(err) = channel
-        .send_message(&ctx.http, CreateMessage::default().embed(embed))
-        .await
-    {
-        error!("Failed to send message: {err}");
-    }
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::GuildMemberAddition,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
 }
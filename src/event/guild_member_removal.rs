@@ -1,31 +1,57 @@
 use serde::{Deserialize, Serialize};
 use serenity::{
-    all::{ChannelId, CreateEmbedFooter, GuildId, Member, Mentionable, User},
-    builder::{CreateEmbed, CreateMessage},
+    all::{GuildId, Member, Mentionable, User},
     client::Context,
+    model::guild::audit_log::{Action, MemberAction},
 };
-use tracing::{debug, error, instrument};
+use tracing::{debug, instrument};
 
-use crate::{channel, colors::Colors, event::Event, texts::Texts as GlobalTexts};
+use crate::{
+    audit, channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Author, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[
+    ("title", &["user"]),
+    ("title_kicked", &["user"]),
+    ("title_banned", &["user"]),
+    ("member_count", &["count"]),
+];
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Texts {
     title: String,
+    title_kicked: String,
+    title_banned: String,
     id: String,
     account_creation: String,
     member_count: String,
     joined: String,
+    moderator: String,
+    reason: String,
+    no_reason: String,
 }
 
 impl Default for Texts {
     fn default() -> Self {
         Self {
             title: String::from("%user% left this guild"),
+            title_kicked: String::from("%user% was kicked from this guild"),
+            title_banned: String::from("%user% was banned from this guild"),
             id: String::from("ID"),
             account_creation: String::from("Account Creation"),
             member_count: String::from("Member Count: %count%"),
             joined: String::from("Joined"),
+            moderator: String::from("Moderator"),
+            reason: String::from("Reason"),
+            no_reason: String::from("No reason was provided."),
         }
     }
 }
@@ -38,7 +64,8 @@ pub async fn guild_member_removal_event(
     member: Option<Member>,
     texts: &GlobalTexts,
 ) {
-    let Ok(Some(config_id)) = channel::get(&ctx, guild_id, Event::GuildMemberRemoval).await else {
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::GuildMemberRemoval).await
+    else {
         debug!(
             "ChannelId of guild '{}' was either None or Err.",
             guild_id.get()
@@ -46,74 +73,137 @@ pub async fn guild_member_removal_event(
         return;
     };
 
-    if user.bot {
-        debug!("The user who left was a bot.");
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::GuildMemberRemoval,
+        destinations,
+        Subject {
+            user: Some(user.id),
+            roles: member
+                .as_ref()
+                .map(|member| member.roles.clone())
+                .unwrap_or_default(),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    if ignore::is_author_ignored(
+        &ctx,
+        guild_id,
+        Event::GuildMemberRemoval,
+        Author {
+            id: user.id,
+            bot: user.bot,
+            webhook_id: None,
+        },
+    )
+    .await
+    {
+        debug!("The user who left was an ignored bot.");
         return;
     }
 
-    let (member_count, channel) = {
+    let member_count = {
         let Some(guild) = guild_id.to_guild_cached(&ctx) else {
             debug!("Failed to get cached guild '{}'.", guild_id.get());
             return;
         };
 
-        let Some(channel) = guild.channels.get(&ChannelId::from(config_id)) else {
-            debug!(
-                "Failed to get guild '{}' channel '{config_id}'.",
-                guild_id.get()
-            );
-            return;
-        };
+        guild.member_count
+    };
+
+    let entry = audit::find_any_by_target(
+        &ctx,
+        guild_id,
+        &[
+            Action::Member(MemberAction::Kick),
+            Action::Member(MemberAction::BanAdd),
+        ],
+        user.id.get(),
+    )
+    .await;
 
-        (guild.member_count, channel.clone())
+    let title = match entry.as_ref().map(|entry| entry.action) {
+        Some(Action::Member(MemberAction::Kick)) => &texts.guild_member_removal.title_kicked,
+        Some(Action::Member(MemberAction::BanAdd)) => &texts.guild_member_removal.title_banned,
+        _ => &texts.guild_member_removal.title,
     };
 
-    let mut embed = CreateEmbed::default()
-        .title(
-            texts
-                .guild_member_removal
-                .title
-                .replace("%user%", user.global_name.as_ref().unwrap_or(&user.name)),
-        )
+    let mut embed = LogEmbed::default()
+        .title(template::render(
+            title,
+            &Vars::new().user("user", user.global_name.as_ref().unwrap_or(&user.name)),
+        ))
         .description(user.mention().to_string())
         .color(Colors::PRIMARY)
         .field(
+            "id",
             &texts.guild_member_removal.id,
             format!("@{} ({})", user.name, user.id),
             true,
         )
         .field(
+            "account_creation",
             &texts.guild_member_removal.account_creation,
             format!("<t:{}>", user.created_at().timestamp()),
             true,
         )
-        .footer(CreateEmbedFooter::new(
-            texts
-                .guild_member_removal
-                .member_count
-                .replace("%count%", &member_count.to_string()),
+        .footer(template::render(
+            &texts.guild_member_removal.member_count,
+            &Vars::new().set("count", member_count),
         ));
 
     if let Some(url) = user.avatar_url() {
         embed = embed.thumbnail(url);
     }
 
+    if let Some(entry) = entry {
+        embed = embed
+            .field(
+                "moderator",
+                &texts.guild_member_removal.moderator,
+                entry.user_id.mention().to_string(),
+                true,
+            )
+            .field(
+                "reason",
+                &texts.guild_member_removal.reason,
+                entry
+                    .reason
+                    .unwrap_or(texts.guild_member_removal.no_reason.clone()),
+                false,
+            );
+    }
+
     if let Some(Member {
         joined_at: Some(joined_at),
         ..
     }) = member
     {
         embed = embed.field(
+            "joined",
             &texts.guild_member_removal.joined,
             format!("<t:{}:R>", joined_at.timestamp()),
             false,
         );
     }
 
-    if let Err(err) = channel
-        .send_message(&ctx.http, CreateMessage::default().embed(embed))
-        .await
-    {
-        error!("Failed to send message: {err}");
-    }
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::GuildMemberRemoval,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
 }
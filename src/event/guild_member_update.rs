@@ -0,0 +1,342 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{GuildMemberUpdateEvent, Member, Mentionable, RoleId, Timestamp},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Author, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[
+    ("title", &["user"]),
+    ("nick_body", &["old", "new"]),
+    ("timeout_body", &["old", "new"]),
+    ("timeout_added", &["until"]),
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    id: String,
+    none: String,
+    unknown: String,
+    nick: String,
+    nick_body: String,
+    roles_added: String,
+    roles_removed: String,
+    roles: String,
+    timeout: String,
+    timeout_body: String,
+    timeout_added: String,
+    avatar: String,
+    avatar_changed: String,
+    avatar_removed: String,
+    pending: String,
+    pending_completed: String,
+    pending_started: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("%user% was updated"),
+            id: String::from("ID"),
+            none: String::from("None"),
+            unknown: String::from("Unknown"),
+            nick: String::from("Nickname"),
+            nick_body: String::from("%old% → %new%"),
+            roles_added: String::from("Roles Added"),
+            roles_removed: String::from("Roles Removed"),
+            roles: String::from("Roles"),
+            timeout: String::from("Timeout"),
+            timeout_body: String::from("%old% → %new%"),
+            timeout_added: String::from("Timed out until %until|time:F% (%until|time:R%)"),
+            avatar: String::from("Server Avatar"),
+            avatar_changed: String::from("Server avatar was changed"),
+            avatar_removed: String::from("Server avatar was removed"),
+            pending: String::from("Membership Screening"),
+            pending_completed: String::from("Rules were accepted"),
+            pending_started: String::from("Membership screening is pending"),
+        }
+    }
+}
+
+fn timeout_state(until: Option<Timestamp>, texts: &Texts) -> String {
+    match until {
+        Some(until) if until.unix_timestamp() > chrono::Utc::now().timestamp() => template::render(
+            &texts.timeout_added,
+            &Vars::new().set("until", until.unix_timestamp()),
+        ),
+        _ => texts.none.clone(),
+    }
+}
+
+fn changes(
+    old: &Member,
+    event: &GuildMemberUpdateEvent,
+    texts: &Texts,
+) -> Vec<(&'static str, String, String, bool)> {
+    let mut fields = Vec::new();
+
+    if old.nick != event.nick {
+        fields.push((
+            "nick",
+            texts.nick.clone(),
+            template::render(
+                &texts.nick_body,
+                &Vars::new()
+                    .user("old", old.nick.as_ref().unwrap_or(&texts.none))
+                    .user("new", event.nick.as_ref().unwrap_or(&texts.none)),
+            ),
+            false,
+        ));
+    }
+
+    let added = event
+        .roles
+        .iter()
+        .filter(|role| !old.roles.contains(role))
+        .collect::<Vec<_>>();
+
+    if !added.is_empty() {
+        fields.push((
+            "roles_added",
+            texts.roles_added.clone(),
+            mention_roles(&added),
+            false,
+        ));
+    }
+
+    let removed = old
+        .roles
+        .iter()
+        .filter(|role| !event.roles.contains(role))
+        .collect::<Vec<_>>();
+
+    if !removed.is_empty() {
+        fields.push((
+            "roles_removed",
+            texts.roles_removed.clone(),
+            mention_roles(&removed),
+            false,
+        ));
+    }
+
+    if old.communication_disabled_until != event.communication_disabled_until {
+        fields.push((
+            "timeout",
+            texts.timeout.clone(),
+            template::render(
+                &texts.timeout_body,
+                &Vars::new()
+                    .set(
+                        "old",
+                        timeout_state(old.communication_disabled_until, texts),
+                    )
+                    .set(
+                        "new",
+                        timeout_state(event.communication_disabled_until, texts),
+                    ),
+            ),
+            false,
+        ));
+    }
+
+    if old.avatar != event.avatar {
+        let body = match event.avatar {
+            Some(_) => texts.avatar_changed.clone(),
+            None => texts.avatar_removed.clone(),
+        };
+
+        fields.push(("avatar", texts.avatar.clone(), body, false));
+    }
+
+    if old.pending != event.pending {
+        let body = if event.pending {
+            texts.pending_started.clone()
+        } else {
+            texts.pending_completed.clone()
+        };
+
+        fields.push(("pending", texts.pending.clone(), body, false));
+    }
+
+    fields
+}
+
+// Without the cached member nothing can be compared, so the new state is logged instead
+fn current_state(
+    event: &GuildMemberUpdateEvent,
+    texts: &Texts,
+) -> Vec<(&'static str, String, String, bool)> {
+    let mut fields = vec![(
+        "nick",
+        texts.nick.clone(),
+        template::render(
+            &texts.nick_body,
+            &Vars::new()
+                .user("old", &texts.unknown)
+                .user("new", event.nick.as_ref().unwrap_or(&texts.none)),
+        ),
+        false,
+    )];
+
+    let roles = event.roles.iter().collect::<Vec<_>>();
+
+    fields.push((
+        "roles",
+        texts.roles.clone(),
+        if roles.is_empty() {
+            texts.none.clone()
+        } else {
+            mention_roles(&roles)
+        },
+        false,
+    ));
+
+    if event.communication_disabled_until.is_some() {
+        fields.push((
+            "timeout",
+            texts.timeout.clone(),
+            template::render(
+                &texts.timeout_body,
+                &Vars::new().set("old", &texts.unknown).set(
+                    "new",
+                    timeout_state(event.communication_disabled_until, texts),
+                ),
+            ),
+            false,
+        ));
+    }
+
+    fields
+}
+
+#[instrument(skip(ctx, old_if_available, new, event, texts))]
+pub async fn guild_member_update_event(
+    ctx: Context,
+    old_if_available: Option<Member>,
+    new: Option<Member>,
+    event: GuildMemberUpdateEvent,
+    texts: &GlobalTexts,
+) {
+    let guild_id = event.guild_id;
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::GuildMemberUpdate).await
+    else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::GuildMemberUpdate,
+        destinations,
+        Subject {
+            user: Some(event.user.id),
+            roles: event.roles.clone(),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    if ignore::is_author_ignored(
+        &ctx,
+        guild_id,
+        Event::GuildMemberUpdate,
+        Author {
+            id: event.user.id,
+            bot: event.user.bot,
+            webhook_id: None,
+        },
+    )
+    .await
+    {
+        debug!("The updated user was an ignored bot.");
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+    let texts = &texts.guild_member_update;
+
+    let mut fields = match &old_if_available {
+        Some(old) => changes(old, &event, texts),
+        None => {
+            debug!("Old member data was not present, logging the new state.");
+            current_state(&event, texts)
+        }
+    };
+
+    if fields.is_empty() {
+        debug!("No logged member properties changed.");
+        return;
+    }
+
+    let avatar_changed = old_if_available
+        .as_ref()
+        .is_some_and(|old| old.avatar != event.avatar);
+    let user = &event.user;
+
+    fields.push((
+        "id",
+        texts.id.clone(),
+        format!("@{} ({})", user.name, user.id),
+        false,
+    ));
+
+    let mut embed = LogEmbed::default()
+        .title(template::render(
+            &texts.title,
+            &Vars::new().user("user", user.global_name.as_ref().unwrap_or(&user.name)),
+        ))
+        .description(user.mention().to_string())
+        .color(Colors::PRIMARY)
+        .fields(fields);
+
+    if let Some(url) = user.avatar_url() {
+        embed = embed.thumbnail(url);
+    }
+
+    if avatar_changed {
+        if let Some(url) = new.and_then(|new| new.avatar_url()) {
+            embed = embed.image(url);
+        }
+    }
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::GuildMemberUpdate,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
+
+fn mention_roles(roles: &[&RoleId]) -> String {
+    roles
+        .iter()
+        .map(|role| role.mention().to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{Mentionable, Role},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+    utils::permissions,
+};
+
+pub const VARIABLES: Variables = &[("title", &["role_name"])];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    color: String,
+    hoist: String,
+    mentionable: String,
+    permissions: String,
+    enabled: String,
+    disabled: String,
+    none: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("Role %role_name% was created"),
+            color: String::from("Color"),
+            hoist: String::from("Displayed Separately"),
+            mentionable: String::from("Mentionable"),
+            permissions: String::from("Permissions"),
+            enabled: String::from("Yes"),
+            disabled: String::from("No"),
+            none: String::from("None"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, role, texts))]
+pub async fn guild_role_create_event(ctx: Context, role: Role, texts: &GlobalTexts) {
+    let guild_id = role.guild_id;
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::GuildRoleCreate).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::GuildRoleCreate,
+        destinations,
+        Subject {
+            roles: vec![role.id],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+    let texts = &texts.guild_role_create;
+
+    let state = |value: bool| {
+        if value {
+            texts.enabled.clone()
+        } else {
+            texts.disabled.clone()
+        }
+    };
+
+    let permissions = if role.permissions.is_empty() {
+        texts.none.clone()
+    } else {
+        permissions::names(role.permissions)
+    };
+
+    let embed = LogEmbed::default()
+        .title(template::render(
+            &texts.title,
+            &Vars::new().user("role_name", &role.name),
+        ))
+        .description(role.mention().to_string())
+        .color(Colors::PRIMARY)
+        .field(
+            "color",
+            &texts.color,
+            format!("`#{}`", role.colour.hex()),
+            true,
+        )
+        .field("hoist", &texts.hoist, state(role.hoist), true)
+        .field(
+            "mentionable",
+            &texts.mentionable,
+            state(role.mentionable),
+            true,
+        )
+        .field("permissions", &texts.permissions, permissions, false)
+        .field("id", &texts.id, format!("`{}`", role.id.get()), false);
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::GuildRoleCreate,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
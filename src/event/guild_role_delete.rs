@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{GuildId, Role, RoleId},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[("title", &["role_name"])];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    unknown_title: String,
+    color: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("Role %role_name% was deleted"),
+            unknown_title: String::from("A role was deleted"),
+            color: String::from("Color"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, guild_id, role_id, role, texts))]
+pub async fn guild_role_delete_event(
+    ctx: Context,
+    guild_id: GuildId,
+    role_id: RoleId,
+    role: Option<Role>,
+    texts: &GlobalTexts,
+) {
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::GuildRoleDelete).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::GuildRoleDelete,
+        destinations,
+        Subject {
+            roles: vec![role_id],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    let mut embed = LogEmbed::default().color(Colors::PRIMARY);
+
+    match role {
+        Some(role) => {
+            embed = embed
+                .title(template::render(
+                    &texts.guild_role_delete.title,
+                    &Vars::new().user("role_name", &role.name),
+                ))
+                .field(
+                    "color",
+                    &texts.guild_role_delete.color,
+                    format!("`#{}`", role.colour.hex()),
+                    true,
+                );
+        }
+        None => embed = embed.title(&texts.guild_role_delete.unknown_title),
+    }
+
+    embed = embed.field(
+        "id",
+        &texts.guild_role_delete.id,
+        format!("`{}`", role_id.get()),
+        true,
+    );
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::GuildRoleDelete,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
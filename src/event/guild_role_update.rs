@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{Mentionable, Role},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+    utils::permissions,
+};
+
+pub const VARIABLES: Variables = &[("title", &["role_name"]), ("change_body", &["old", "new"])];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    name: String,
+    color: String,
+    hoist: String,
+    mentionable: String,
+    position: String,
+    permissions_added: String,
+    permissions_removed: String,
+    change_body: String,
+    enabled: String,
+    disabled: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("Role %role_name% was updated"),
+            name: String::from("Name"),
+            color: String::from("Color"),
+            hoist: String::from("Displayed Separately"),
+            mentionable: String::from("Mentionable"),
+            position: String::from("Position"),
+            permissions_added: String::from("Permissions Added"),
+            permissions_removed: String::from("Permissions Removed"),
+            change_body: String::from("%old% → %new%"),
+            enabled: String::from("Yes"),
+            disabled: String::from("No"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, old, new, texts))]
+pub async fn guild_role_update_event(
+    ctx: Context,
+    old: Option<Role>,
+    new: Role,
+    texts: &GlobalTexts,
+) {
+    let guild_id = new.guild_id;
+
+    let Some(old) = old else {
+        debug!("Old role data was not present.");
+        return;
+    };
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::GuildRoleUpdate).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::GuildRoleUpdate,
+        destinations,
+        Subject {
+            roles: vec![new.id],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+    let texts = &texts.guild_role_update;
+    let mut fields = Vec::new();
+
+    let change = |old: &str, new: &str| {
+        template::render(
+            &texts.change_body,
+            &Vars::new().set("old", old).set("new", new),
+        )
+    };
+
+    let state = |value: bool| {
+        if value {
+            &texts.enabled
+        } else {
+            &texts.disabled
+        }
+    };
+
+    if old.name != new.name {
+        fields.push((
+            "name",
+            texts.name.clone(),
+            change(&template::escape(&old.name), &template::escape(&new.name)),
+            false,
+        ));
+    }
+
+    if old.colour != new.colour {
+        fields.push((
+            "color",
+            texts.color.clone(),
+            change(
+                &format!("`#{}`", old.colour.hex()),
+                &format!("`#{}`", new.colour.hex()),
+            ),
+            true,
+        ));
+    }
+
+    if old.hoist != new.hoist {
+        fields.push((
+            "hoist",
+            texts.hoist.clone(),
+            change(state(old.hoist), state(new.hoist)),
+            true,
+        ));
+    }
+
+    if old.mentionable != new.mentionable {
+        fields.push((
+            "mentionable",
+            texts.mentionable.clone(),
+            change(state(old.mentionable), state(new.mentionable)),
+            true,
+        ));
+    }
+
+    if old.position != new.position {
+        fields.push((
+            "position",
+            texts.position.clone(),
+            change(&old.position.to_string(), &new.position.to_string()),
+            true,
+        ));
+    }
+
+    let added = new.permissions - old.permissions;
+    if !added.is_empty() {
+        fields.push((
+            "permissions_added",
+            texts.permissions_added.clone(),
+            permissions::names(added),
+            false,
+        ));
+    }
+
+    let removed = old.permissions - new.permissions;
+    if !removed.is_empty() {
+        fields.push((
+            "permissions_removed",
+            texts.permissions_removed.clone(),
+            permissions::names(removed),
+            false,
+        ));
+    }
+
+    if fields.is_empty() {
+        debug!("No logged role properties changed.");
+        return;
+    }
+
+    fields.push(("id", texts.id.clone(), format!("`{}`", new.id.get()), false));
+
+    let embed = LogEmbed::default()
+        .title(template::render(
+            &texts.title,
+            &Vars::new().user("role_name", &new.name),
+        ))
+        .description(new.mention().to_string())
+        .color(Colors::PRIMARY)
+        .fields(fields);
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::GuildRoleUpdate,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
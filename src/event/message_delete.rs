@@ -1,22 +1,42 @@
 use serde::{Deserialize, Serialize};
 use serenity::{
     all::{ChannelId, GuildId, MessageId},
-    builder::{CreateEmbed, CreateEmbedAuthor, CreateMessage},
     client::Context,
 };
-use tracing::{debug, error, instrument};
+use tracing::{debug, instrument};
 
 use crate::{
-    channel, colors::Colors, event::Event, texts::Texts as GlobalTexts, utils::text::into_blocks,
+    channel,
+    colors::Colors,
+    data::Data,
+    delivery,
+    event::Event,
+    ignore::{self, Author, Subject},
+    layout::LogEmbed,
+    store::{self, StoredMessage},
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+    utils::text::{format_size, into_blocks},
 };
 
 const MAX_FIELD_SIZE: usize = 1024;
 
+pub const VARIABLES: Variables = &[
+    ("description", &["channel"]),
+    ("content", &["i"]),
+    ("attachment_line", &["filename", "url", "size"]),
+    ("id_body", &["user_id", "message_id"]),
+];
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Texts {
     description: String,
     content: String,
+    attachments: String,
+    attachment_line: String,
+    stickers: String,
+    embeds: String,
     date: String,
     id: String,
     id_body: String,
@@ -27,6 +47,10 @@ impl Default for Texts {
         Self {
             description: String::from("Message deleted in %channel%"),
             content: String::from("Content N.%i%"),
+            attachments: String::from("Attachments"),
+            attachment_line: String::from("[%filename%](%url%) (%size%)"),
+            stickers: String::from("Stickers"),
+            embeds: String::from("Embeds"),
             date: String::from("Date"),
             id: String::from("ID"),
             id_body: String::from("```toml\nUser = %user_id%\nMessage = %message_id%\n```"),
@@ -49,21 +73,51 @@ pub async fn message_delete_event(
         return;
     };
 
-    let (content, author) = {
-        let Some(message) = ctx.cache.message(channel_id, message_id) else {
-            debug!("Message was not in cache.");
-            return;
-        };
+    let stored = {
+        let data = ctx.data.read().await;
+        let data = data.get::<Data>().expect("Data should never be none.");
 
-        if message.author.bot {
-            debug!("The message author was a bot.");
-            return;
-        }
+        data.store.remove(channel_id.get(), message_id.get()).await
+    };
 
-        (message.content.clone(), message.author.clone())
+    let cached = ctx
+        .cache
+        .message(channel_id, message_id)
+        .map(|message| StoredMessage::from(&*message));
+
+    let Some(StoredMessage {
+        content,
+        author,
+        attachments,
+        stickers,
+        embeds,
+        webhook_id,
+        ..
+    }) = cached.or(stored)
+    else {
+        debug!("Message was neither in cache nor in store.");
+        return;
     };
 
-    let Ok(Some(config_id)) = channel::get(&ctx, guild_id, Event::MessageDelete).await else {
+    let files = store::take_attachments(&attachments);
+
+    if ignore::is_author_ignored(
+        &ctx,
+        guild_id,
+        Event::MessageDelete,
+        Author {
+            id: author.id.into(),
+            bot: author.bot,
+            webhook_id,
+        },
+    )
+    .await
+    {
+        debug!("The message author was an ignored bot.");
+        return;
+    }
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::MessageDelete).await else {
         debug!(
             "ChannelId of guild '{}' was either None or Err.",
             guild_id.get()
@@ -71,80 +125,123 @@ pub async fn message_delete_event(
         return;
     };
 
-    let channel = {
-        let Some(guild) = guild_id.to_guild_cached(&ctx) else {
-            debug!("Failed to get cached guild '{}'.", guild_id.get());
-            return;
-        };
-
-        let Some(channel) = guild.channels.get(&ChannelId::from(config_id)) else {
-            debug!(
-                "Failed to get guild '{}' channel '{config_id}'.",
-                guild_id.get()
-            );
-            return;
-        };
-
-        channel.clone()
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::MessageDelete,
+        destinations,
+        Subject {
+            channels: vec![channel_id],
+            user: Some(author.id.into()),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    let author_name = match guild_id.member(&ctx, author.id).await {
+        Ok(member) => member.display_name().to_string(),
+        Err(_) => author.display_name().to_string(),
     };
 
-    let mut embed_author = CreateEmbedAuthor::new(
-        author
-            .nick_in(&ctx.http, guild_id)
-            .await
-            .unwrap_or(author.global_name.clone().unwrap_or(author.name.clone())),
+    let description = template::render(
+        &texts.message_delete.description,
+        &Vars::new().set("channel", format!("<#{}>", channel_id.get())),
+    );
+
+    let id_body = template::render(
+        &texts.message_delete.id_body,
+        &Vars::new()
+            .set("user_id", author.id)
+            .set("message_id", message_id.get()),
     );
 
-    if let Some(avatar) = author.avatar_url() {
-        embed_author = embed_author.icon_url(avatar);
+    let mut fields = Vec::new();
+
+    if !content.is_empty() {
+        fields.extend(
+            into_blocks(&content, MAX_FIELD_SIZE)
+                .into_iter()
+                .enumerate()
+                .map(|(i, content)| {
+                    (
+                        "content",
+                        template::render(
+                            &texts.message_delete.content,
+                            &Vars::new().set("i", i + 1),
+                        ),
+                        content,
+                        false,
+                    )
+                }),
+        );
     }
 
-    let description = texts
-        .message_delete
-        .description
-        .replace("%channel%", &format!("<#{}>", channel_id.get()));
-
-    let id_body = texts
-        .message_delete
-        .id_body
-        .replace("%user_id%", &author.id.get().to_string())
-        .replace("%message_id%", &message_id.get().to_string());
-
-    let mut fields = into_blocks(&content, MAX_FIELD_SIZE)
-        .into_iter()
-        .enumerate()
-        .map(|(i, content)| {
-            (
-                texts
-                    .message_delete
-                    .content
-                    .replace("%i%", &(i + 1).to_string()),
-                content,
-                false,
-            )
-        })
-        .collect::<Vec<_>>();
+    if !attachments.is_empty() {
+        let lines = attachments
+            .iter()
+            .map(|attachment| {
+                template::render(
+                    &texts.message_delete.attachment_line,
+                    &Vars::new()
+                        .user("filename", &attachment.filename)
+                        .set("url", &attachment.url)
+                        .set("size", format_size(attachment.size.into())),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        fields.push((
+            "attachments",
+            texts.message_delete.attachments.clone(),
+            lines.join("\n"),
+            false,
+        ));
+    }
+
+    if !stickers.is_empty() {
+        fields.push((
+            "stickers",
+            texts.message_delete.stickers.clone(),
+            stickers.join(", "),
+            false,
+        ));
+    }
+
+    if !embeds.is_empty() {
+        fields.push((
+            "embeds",
+            texts.message_delete.embeds.clone(),
+            embeds.join("\n"),
+            false,
+        ));
+    }
 
     fields.push((
+        "date",
         texts.message_delete.date.clone(),
         format!("<t:{timestamp}:F>"),
         false,
     ));
-    fields.push((texts.message_delete.id.clone(), id_body, false));
-
-    if let Err(err) = channel
-        .send_message(
-            &ctx.http,
-            CreateMessage::default().embed(
-                CreateEmbed::default()
-                    .color(Colors::PRIMARY)
-                    .author(embed_author)
-                    .description(description)
-                    .fields(fields),
-            ),
-        )
-        .await
-    {
-        error!("Failed to send message: {err}");
-    }
+    fields.push(("id", texts.message_delete.id.clone(), id_body, false));
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::MessageDelete,
+        &destinations,
+        vec![LogEmbed::default()
+            .color(Colors::PRIMARY)
+            .author(author_name, author.avatar_url.clone())
+            .description(description)
+            .fields(fields)],
+        files,
+    )
+    .await;
 }
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{ChannelId, CreateAttachment, GuildId, MessageId},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    data::Data,
+    delivery,
+    event::Event,
+    ignore::{self, is_author_excluded, Author, Subject},
+    layout::LogEmbed,
+    store::{self, StoredMessage},
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[
+    ("description", &["count", "channel"]),
+    ("count_body", &["recovered", "count"]),
+    ("time_range_body", &["from", "to"]),
+    ("transcript_name", &["channel_id"]),
+    ("transcript_line", &["date", "user", "user_id", "content"]),
+    ("transcript_attachment", &["filename", "url"]),
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    description: String,
+    count: String,
+    count_body: String,
+    time_range: String,
+    time_range_body: String,
+    date: String,
+    transcript_name: String,
+    transcript_line: String,
+    transcript_attachment: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            description: String::from("%count% messages were deleted in %channel%"),
+            count: String::from("Count"),
+            count_body: String::from("%recovered% of %count% messages recovered"),
+            time_range: String::from("Time Range"),
+            time_range_body: String::from("%from|time:F% - %to|time:F%"),
+            date: String::from("Date"),
+            transcript_name: String::from("transcript-%channel_id%.txt"),
+            transcript_line: String::from("[%date%] %user% (%user_id%): %content%"),
+            transcript_attachment: String::from("    + %filename% (%url%)"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, channel_id, message_ids, guild_id, texts))]
+pub async fn message_delete_bulk_event(
+    ctx: Context,
+    channel_id: ChannelId,
+    mut message_ids: Vec<MessageId>,
+    guild_id: Option<GuildId>,
+    texts: &GlobalTexts,
+) {
+    let timestamp = chrono::Utc::now().timestamp();
+
+    let Some(guild_id) = guild_id else {
+        debug!("Messages deleted in DMs.");
+        return;
+    };
+
+    message_ids.sort();
+
+    let (Some(first), Some(last)) = (message_ids.first(), message_ids.last()) else {
+        debug!("No message ids were received.");
+        return;
+    };
+
+    let (from, to) = (
+        first.created_at().unix_timestamp(),
+        last.created_at().unix_timestamp(),
+    );
+
+    let mut messages = Vec::with_capacity(message_ids.len());
+    {
+        let data = ctx.data.read().await;
+        let data = data.get::<Data>().expect("Data should never be none.");
+
+        let config = data.cache.get_or_insert(guild_id.get()).await.ok();
+
+        for message_id in &message_ids {
+            let stored = data.store.remove(channel_id.get(), message_id.get()).await;
+            let cached = ctx
+                .cache
+                .message(channel_id, *message_id)
+                .map(|message| StoredMessage::from(&*message));
+
+            if let Some(message) = cached.or(stored) {
+                store::remove_attachments(&message.attachments);
+
+                let author = Author {
+                    id: message.author.id.into(),
+                    bot: message.author.bot,
+                    webhook_id: message.webhook_id,
+                };
+
+                if config.as_ref().is_some_and(|config| {
+                    is_author_excluded(&ctx, config, Event::MessageDeleteBulk, &author)
+                }) {
+                    continue;
+                }
+
+                messages.push(message);
+            }
+        }
+    }
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::MessageDeleteBulk).await
+    else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::MessageDeleteBulk,
+        destinations,
+        Subject {
+            channels: vec![channel_id],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    let count = message_ids.len();
+
+    let description = template::render(
+        &texts.message_delete_bulk.description,
+        &Vars::new()
+            .set("count", count)
+            .set("channel", format!("<#{}>", channel_id.get())),
+    );
+
+    let count_body = template::render(
+        &texts.message_delete_bulk.count_body,
+        &Vars::new()
+            .set("recovered", messages.len())
+            .set("count", count),
+    );
+
+    let time_range_body = template::render(
+        &texts.message_delete_bulk.time_range_body,
+        &Vars::new().set("from", from).set("to", to),
+    );
+
+    let embed = LogEmbed::default()
+        .color(Colors::PRIMARY)
+        .description(description)
+        .field("count", &texts.message_delete_bulk.count, count_body, false)
+        .field(
+            "time_range",
+            &texts.message_delete_bulk.time_range,
+            time_range_body,
+            false,
+        )
+        .field(
+            "date",
+            &texts.message_delete_bulk.date,
+            format!("<t:{timestamp}:F>"),
+            false,
+        );
+
+    let mut files = Vec::new();
+
+    if !messages.is_empty() {
+        let transcript = messages
+            .iter()
+            .map(|message| transcript_line(message, &texts))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let name = template::render(
+            &texts.message_delete_bulk.transcript_name,
+            &Vars::new().set("channel_id", channel_id.get()),
+        );
+
+        files.push(CreateAttachment::bytes(transcript.into_bytes(), name));
+    }
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::MessageDeleteBulk,
+        &destinations,
+        vec![embed],
+        files,
+    )
+    .await;
+}
+
+fn transcript_line(message: &StoredMessage, texts: &GlobalTexts) -> String {
+    let date = MessageId::new(message.id)
+        .created_at()
+        .format("%Y-%m-%d %H:%M:%S UTC")
+        .to_string();
+
+    let mut lines = vec![template::render(
+        &texts.message_delete_bulk.transcript_line,
+        &Vars::new()
+            .set("date", date)
+            .set("user", &message.author.name)
+            .set("user_id", message.author.id)
+            .set("content", &message.content),
+    )];
+
+    for attachment in &message.attachments {
+        lines.push(template::render(
+            &texts.message_delete_bulk.transcript_attachment,
+            &Vars::new()
+                .set("filename", &attachment.filename)
+                .set("url", &attachment.url),
+        ));
+    }
+
+    lines.join("\n")
+}
@@ -1,23 +1,47 @@
 use serde::{Deserialize, Serialize};
 use serenity::{
-    all::{ChannelId, Message, MessageUpdateEvent},
-    builder::{CreateEmbed, CreateEmbedAuthor, CreateMessage},
+    all::{Message, MessageUpdateEvent},
     client::Context,
 };
 use tracing::{debug, error, instrument};
 
 use crate::{
-    channel, colors::Colors, event::Event, texts::Texts as GlobalTexts, utils::text::into_blocks,
+    channel,
+    colors::Colors,
+    data::Data,
+    delivery,
+    event::Event,
+    ignore::{self, Author, Subject},
+    layout::LogEmbed,
+    store::{self, StoredAttachment, StoredMessage},
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+    utils::{
+        diff::{self, DiffMode},
+        text::{format_size, into_blocks},
+    },
 };
 
 const MAX_FIELD_SIZE: usize = 1024;
 
+pub const VARIABLES: Variables = &[
+    ("description", &["channel", "link"]),
+    ("now", &["i"]),
+    ("previous", &["i"]),
+    ("changes", &["i"]),
+    ("attachment_line", &["filename", "url", "size"]),
+    ("id_body", &["user_id", "message_id"]),
+];
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Texts {
     description: String,
     now: String,
     previous: String,
+    changes: String,
+    removed_attachments: String,
+    attachment_line: String,
     date: String,
     id: String,
     id_body: String,
@@ -29,6 +53,9 @@ impl Default for Texts {
             description: String::from("Updated their [message](%link%) in %channel%"),
             now: String::from("Now N.%i%"),
             previous: String::from("Previous N.%i%"),
+            changes: String::from("Changes N.%i%"),
+            removed_attachments: String::from("Removed Attachments"),
+            attachment_line: String::from("[%filename%](%url%) (%size%)"),
             date: String::from("Date"),
             id: String::from("ID"),
             id_body: String::from("```toml\nUser = %user_id%\nMessage = %message_id%\n```"),
@@ -51,27 +78,98 @@ pub async fn message_update_event(
         return;
     };
 
-    let Some(old) = old_if_available else {
-        debug!("Old message data was not present.");
-        return;
+    let stored = {
+        let data = ctx.data.read().await;
+        let data = data.get::<Data>().expect("Data should never be none.");
+
+        let stored = data.store.get(event.channel_id.get(), event.id.get()).await;
+
+        if let Some(content) = &event.content {
+            data.store
+                .update_content(event.channel_id.get(), event.id.get(), content)
+                .await;
+        }
+
+        if let Some(attachments) = &event.attachments {
+            data.store
+                .update_attachments(
+                    event.channel_id.get(),
+                    event.id.get(),
+                    attachments.iter().map(StoredAttachment::from).collect(),
+                )
+                .await;
+        }
+
+        stored
     };
 
-    let Some(new) = new else {
-        debug!("New message data was not present.");
+    let Some(old) = old_if_available
+        .map(|old| StoredMessage::from(&old))
+        .or_else(|| stored.clone())
+    else {
+        debug!("Old message data was neither in cache nor in store.");
         return;
     };
 
-    if new.content == old.content {
-        debug!("The content of the old message was the same as the content of the new message.");
+    let new = match new {
+        Some(new) => StoredMessage::from(&new),
+        None => {
+            let Some(mut stored) =
+                stored.filter(|_| event.content.is_some() || event.attachments.is_some())
+            else {
+                debug!("New message data was not present.");
+                return;
+            };
+
+            if let Some(content) = &event.content {
+                stored.content = content.clone();
+            }
+
+            if let Some(attachments) = &event.attachments {
+                stored.attachments = attachments.iter().map(StoredAttachment::from).collect();
+            }
+
+            stored
+        }
+    };
+
+    let removed = old
+        .attachments
+        .iter()
+        .filter(|attachment| {
+            !new.attachments
+                .iter()
+                .any(|new_attachment| new_attachment.id == attachment.id)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let content_changed = new.content != old.content;
+
+    if !content_changed && removed.is_empty() {
+        debug!("Neither the content nor the attachments of the message changed.");
         return;
     }
 
-    if new.author.bot {
-        debug!("The message author was a bot.");
+    let files = store::take_attachments(&removed);
+
+    if ignore::is_author_ignored(
+        &ctx,
+        guild_id,
+        Event::MessageUpdate,
+        Author {
+            id: new.author.id.into(),
+            bot: new.author.bot,
+            webhook_id: new.webhook_id,
+        },
+    )
+    .await
+    {
+        debug!("The message author was an ignored bot.");
         return;
     }
 
-    let Ok(Some(config_id)) = channel::get(&ctx, guild_id, Event::MessageUpdate).await else {
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::MessageUpdate).await else {
         debug!(
             "ChannelId of guild '{}' was either None or Err.",
             guild_id.get()
@@ -79,97 +177,141 @@ pub async fn message_update_event(
         return;
     };
 
-    let channel = {
-        let Some(guild) = guild_id.to_guild_cached(&ctx) else {
-            debug!("Failed to get cached guild '{}'.", guild_id.get());
-            return;
-        };
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::MessageUpdate,
+        destinations,
+        Subject {
+            channels: vec![event.channel_id],
+            user: Some(new.author.id.into()),
+            ..Default::default()
+        },
+    )
+    .await;
 
-        let Some(channel) = guild.channels.get(&ChannelId::from(config_id)) else {
-            debug!(
-                "Failed to get guild '{}' channel '{config_id}'.",
-                guild_id.get()
-            );
-            return;
-        };
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
 
-        channel.clone()
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    let author_name = match guild_id.member(&ctx, new.author.id).await {
+        Ok(member) => member.display_name().to_string(),
+        Err(_) => new.author.display_name().to_string(),
     };
 
-    let mut embed_author = CreateEmbedAuthor::new(
-        new.author.nick_in(&ctx.http, guild_id).await.unwrap_or(
-            new.author
-                .global_name
-                .clone()
-                .unwrap_or(new.author.name.clone()),
-        ),
+    let description = template::render(
+        &texts.message_update.description,
+        &Vars::new()
+            .set("channel", format!("<#{}>", event.channel_id.get()))
+            .set("link", event.id.link(event.channel_id, Some(guild_id))),
     );
 
-    if let Some(avatar) = new.author.avatar_url() {
-        embed_author = embed_author.icon_url(avatar);
-    }
+    let id_body = template::render(
+        &texts.message_update.id_body,
+        &Vars::new()
+            .set("user_id", new.author.id)
+            .set("message_id", new.id),
+    );
 
-    let description = texts
-        .message_update
-        .description
-        .replace("%channel%", &format!("<#{}>", event.channel_id.get()))
-        .replace("%link%", &new.link());
-
-    let id_body = texts
-        .message_update
-        .id_body
-        .replace("%user_id%", &new.author.id.get().to_string())
-        .replace("%message_id%", &new.id.get().to_string());
-
-    let mut fields = into_blocks(&new.content, MAX_FIELD_SIZE)
-        .into_iter()
-        .enumerate()
-        .map(|(i, content)| {
-            (
-                texts
-                    .message_update
-                    .now
-                    .replace("%i%", &(i + 1).to_string()),
-                content,
-                false,
-            )
-        })
-        .collect::<Vec<_>>();
+    let diff_mode = {
+        let data = ctx.data.read().await;
+        let data = data.get::<Data>().expect("Data should never be none.");
+
+        match data.cache.get_or_insert(guild_id.get()).await {
+            Ok(config) => config.diff_mode(),
+            Err(err) => {
+                error!("Failed to get GuildConfig: {err:?}");
+                DiffMode::default()
+            }
+        }
+    };
+
+    let numbered = |id: &'static str, name: &str, blocks: Vec<String>| {
+        blocks
+            .into_iter()
+            .enumerate()
+            .map(|(i, content)| {
+                (
+                    id,
+                    template::render(name, &Vars::new().set("i", i + 1)),
+                    content,
+                    false,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut fields = match diff_mode {
+        _ if !content_changed => Vec::new(),
+        DiffMode::Full => {
+            let mut fields = numbered(
+                "now",
+                &texts.message_update.now,
+                into_blocks(&new.content, MAX_FIELD_SIZE),
+            );
+            fields.extend(numbered(
+                "previous",
+                &texts.message_update.previous,
+                into_blocks(&old.content, MAX_FIELD_SIZE),
+            ));
+            fields
+        }
+        DiffMode::Inline => numbered(
+            "changes",
+            &texts.message_update.changes,
+            diff::inline(&old.content, &new.content, MAX_FIELD_SIZE),
+        ),
+        DiffMode::Block => numbered(
+            "changes",
+            &texts.message_update.changes,
+            diff::block(&old.content, &new.content, MAX_FIELD_SIZE),
+        ),
+    };
+
+    if !removed.is_empty() {
+        let lines = removed
+            .iter()
+            .map(|attachment| {
+                template::render(
+                    &texts.message_update.attachment_line,
+                    &Vars::new()
+                        .user("filename", &attachment.filename)
+                        .set("url", &attachment.url)
+                        .set("size", format_size(attachment.size.into())),
+                )
+            })
+            .collect::<Vec<_>>();
 
-    for (i, content) in into_blocks(&old.content, MAX_FIELD_SIZE)
-        .into_iter()
-        .enumerate()
-    {
         fields.push((
-            texts
-                .message_update
-                .previous
-                .replace("%i%", &(i + 1).to_string()),
-            content,
+            "removed_attachments",
+            texts.message_update.removed_attachments.clone(),
+            lines.join("\n"),
             false,
-        ))
+        ));
     }
 
     fields.push((
+        "date",
         texts.message_update.date.clone(),
         format!("<t:{timestamp}:F>"),
         false,
     ));
-    fields.push((texts.message_update.id.clone(), id_body, false));
-
-    if let Err(err) = channel
-        .send_message(
-            &ctx.http,
-            CreateMessage::default().embed(
-                CreateEmbed::default()
-                    .color(Colors::PRIMARY)
-                    .author(embed_author)
-                    .description(description)
-                    .fields(fields),
-            ),
-        )
-        .await
-    {
-        error!("Failed to send message: {err}");
-    }
+    fields.push(("id", texts.message_update.id.clone(), id_body, false));
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::MessageUpdate,
+        &destinations,
+        vec![LogEmbed::default()
+            .color(Colors::PRIMARY)
+            .author(author_name, new.author.avatar_url.clone())
+            .description(description)
+            .fields(fields)],
+        files,
+    )
+    .await;
 }
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{GuildChannel, Mentionable},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[("title", &["channel_name"])];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    kind: String,
+    channel: String,
+    owner: String,
+    none: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("Thread %channel_name% was created"),
+            kind: String::from("Type"),
+            channel: String::from("Channel"),
+            owner: String::from("Owner"),
+            none: String::from("None"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, thread, texts))]
+pub async fn thread_create_event(ctx: Context, thread: GuildChannel, texts: &GlobalTexts) {
+    let guild_id = thread.guild_id;
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::ThreadCreate).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::ThreadCreate,
+        destinations,
+        Subject {
+            channels: [Some(thread.id), thread.parent_id]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    let embed = LogEmbed::default()
+        .title(template::render(
+            &texts.thread_create.title,
+            &Vars::new().user("channel_name", &thread.name),
+        ))
+        .description(thread.mention().to_string())
+        .color(Colors::PRIMARY)
+        .field(
+            "kind",
+            &texts.thread_create.kind,
+            format!("`{}`", thread.kind.name()),
+            true,
+        )
+        .field(
+            "channel",
+            &texts.thread_create.channel,
+            thread
+                .parent_id
+                .map(|id| id.mention().to_string())
+                .unwrap_or(texts.thread_create.none.clone()),
+            true,
+        )
+        .field(
+            "owner",
+            &texts.thread_create.owner,
+            thread
+                .owner_id
+                .map(|id| id.mention().to_string())
+                .unwrap_or(texts.thread_create.none.clone()),
+            true,
+        )
+        .field(
+            "id",
+            &texts.thread_create.id,
+            format!("`{}`", thread.id.get()),
+            true,
+        );
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::ThreadCreate,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{GuildChannel, Mentionable, PartialGuildChannel},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[("title", &["channel_name"])];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    unknown_title: String,
+    kind: String,
+    channel: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("Thread %channel_name% was deleted"),
+            unknown_title: String::from("A thread was deleted"),
+            kind: String::from("Type"),
+            channel: String::from("Channel"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, thread, full_thread_data, texts))]
+pub async fn thread_delete_event(
+    ctx: Context,
+    thread: PartialGuildChannel,
+    full_thread_data: Option<GuildChannel>,
+    texts: &GlobalTexts,
+) {
+    let guild_id = thread.guild_id;
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::ThreadDelete).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::ThreadDelete,
+        destinations,
+        Subject {
+            channels: vec![thread.id, thread.parent_id],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    let title = match &full_thread_data {
+        Some(data) => template::render(
+            &texts.thread_delete.title,
+            &Vars::new().user("channel_name", &data.name),
+        ),
+        None => texts.thread_delete.unknown_title.clone(),
+    };
+
+    let embed = LogEmbed::default()
+        .title(title)
+        .color(Colors::PRIMARY)
+        .field(
+            "kind",
+            &texts.thread_delete.kind,
+            format!("`{}`", thread.kind.name()),
+            true,
+        )
+        .field(
+            "channel",
+            &texts.thread_delete.channel,
+            thread.parent_id.mention().to_string(),
+            true,
+        )
+        .field(
+            "id",
+            &texts.thread_delete.id,
+            format!("`{}`", thread.id.get()),
+            true,
+        );
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::ThreadDelete,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{GuildChannel, Mentionable},
+    client::Context,
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+pub const VARIABLES: Variables = &[
+    ("title", &["channel_name"]),
+    ("change_body", &["old", "new"]),
+    ("seconds", &["seconds"]),
+    ("minutes", &["minutes"]),
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    title: String,
+    name: String,
+    archived: String,
+    locked: String,
+    slowmode: String,
+    auto_archive: String,
+    change_body: String,
+    enabled: String,
+    disabled: String,
+    seconds: String,
+    minutes: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            title: String::from("Thread %channel_name% was updated"),
+            name: String::from("Name"),
+            archived: String::from("Archived"),
+            locked: String::from("Locked"),
+            slowmode: String::from("Slowmode"),
+            auto_archive: String::from("Auto Archive"),
+            change_body: String::from("%old% → %new%"),
+            enabled: String::from("Yes"),
+            disabled: String::from("No"),
+            seconds: String::from("%seconds%s"),
+            minutes: String::from("%minutes% minutes"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+#[instrument(skip(ctx, old, new, texts))]
+pub async fn thread_update_event(
+    ctx: Context,
+    old: Option<GuildChannel>,
+    new: GuildChannel,
+    texts: &GlobalTexts,
+) {
+    let guild_id = new.guild_id;
+
+    let Some(old) = old else {
+        debug!("Old thread data was not present.");
+        return;
+    };
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::ThreadUpdate).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::ThreadUpdate,
+        destinations,
+        Subject {
+            channels: [Some(new.id), new.parent_id]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+    let texts = &texts.thread_update;
+    let mut fields = Vec::new();
+
+    let change = |old: &str, new: &str| {
+        template::render(
+            &texts.change_body,
+            &Vars::new().set("old", old).set("new", new),
+        )
+    };
+
+    let state = |value: bool| {
+        if value {
+            &texts.enabled
+        } else {
+            &texts.disabled
+        }
+    };
+
+    if old.name != new.name {
+        fields.push((
+            "name",
+            texts.name.clone(),
+            change(&template::escape(&old.name), &template::escape(&new.name)),
+            false,
+        ));
+    }
+
+    if let (Some(old_metadata), Some(new_metadata)) = (old.thread_metadata, new.thread_metadata) {
+        if old_metadata.archived != new_metadata.archived {
+            fields.push((
+                "archived",
+                texts.archived.clone(),
+                change(state(old_metadata.archived), state(new_metadata.archived)),
+                true,
+            ));
+        }
+
+        if old_metadata.locked != new_metadata.locked {
+            fields.push((
+                "locked",
+                texts.locked.clone(),
+                change(state(old_metadata.locked), state(new_metadata.locked)),
+                true,
+            ));
+        }
+
+        if old_metadata.auto_archive_duration != new_metadata.auto_archive_duration {
+            let minutes = |duration: u16| {
+                template::render(&texts.minutes, &Vars::new().set("minutes", duration))
+            };
+
+            fields.push((
+                "auto_archive",
+                texts.auto_archive.clone(),
+                change(
+                    &minutes(old_metadata.auto_archive_duration.into()),
+                    &minutes(new_metadata.auto_archive_duration.into()),
+                ),
+                true,
+            ));
+        }
+    }
+
+    if old.rate_limit_per_user != new.rate_limit_per_user {
+        let seconds = |seconds: Option<u16>| match seconds {
+            Some(seconds) if seconds > 0 => {
+                template::render(&texts.seconds, &Vars::new().set("seconds", seconds))
+            }
+            _ => texts.disabled.clone(),
+        };
+
+        fields.push((
+            "slowmode",
+            texts.slowmode.clone(),
+            change(
+                &seconds(old.rate_limit_per_user),
+                &seconds(new.rate_limit_per_user),
+            ),
+            true,
+        ));
+    }
+
+    if fields.is_empty() {
+        debug!("No logged thread properties changed.");
+        return;
+    }
+
+    fields.push(("id", texts.id.clone(), format!("`{}`", new.id.get()), false));
+
+    let embed = LogEmbed::default()
+        .title(template::render(
+            &texts.title,
+            &Vars::new().user("channel_name", &new.name),
+        ))
+        .description(new.mention().to_string())
+        .color(Colors::PRIMARY)
+        .fields(fields);
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::ThreadUpdate,
+        &destinations,
+        vec![embed],
+        Vec::new(),
+    )
+    .await;
+}
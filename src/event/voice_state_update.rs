@@ -0,0 +1,366 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{ChannelId, GuildId, Mentionable, UserId, VoiceState},
+    client::Context,
+    model::guild::audit_log::{Action, MemberAction},
+};
+use tracing::{debug, instrument};
+
+use crate::{
+    audit, channel,
+    colors::Colors,
+    delivery,
+    event::Event,
+    ignore::{self, Author, Subject},
+    layout::LogEmbed,
+    template::{self, Variables, Vars},
+    texts::{self, Texts as GlobalTexts},
+};
+
+const CHANGE_VARIABLES: &[&str] = &["user", "channel", "old_channel"];
+
+pub const VARIABLES: Variables = &[
+    ("joined", CHANGE_VARIABLES),
+    ("left", CHANGE_VARIABLES),
+    ("disconnected", CHANGE_VARIABLES),
+    ("moved", CHANGE_VARIABLES),
+    ("server_muted", CHANGE_VARIABLES),
+    ("server_unmuted", CHANGE_VARIABLES),
+    ("server_deafened", CHANGE_VARIABLES),
+    ("server_undeafened", CHANGE_VARIABLES),
+    ("self_muted", CHANGE_VARIABLES),
+    ("self_unmuted", CHANGE_VARIABLES),
+    ("self_deafened", CHANGE_VARIABLES),
+    ("self_undeafened", CHANGE_VARIABLES),
+    ("stream_started", CHANGE_VARIABLES),
+    ("stream_stopped", CHANGE_VARIABLES),
+    ("video_started", CHANGE_VARIABLES),
+    ("video_stopped", CHANGE_VARIABLES),
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Texts {
+    joined: String,
+    left: String,
+    disconnected: String,
+    moved: String,
+    server_muted: String,
+    server_unmuted: String,
+    server_deafened: String,
+    server_undeafened: String,
+    self_muted: String,
+    self_unmuted: String,
+    self_deafened: String,
+    self_undeafened: String,
+    stream_started: String,
+    stream_stopped: String,
+    video_started: String,
+    video_stopped: String,
+    moderator: String,
+    id: String,
+}
+
+impl Default for Texts {
+    fn default() -> Self {
+        Self {
+            joined: String::from("%user% joined %channel%"),
+            left: String::from("%user% left %channel%"),
+            disconnected: String::from("%user% was disconnected from %channel%"),
+            moved: String::from("%user% moved from %old_channel% to %channel%"),
+            server_muted: String::from("%user% was server muted in %channel%"),
+            server_unmuted: String::from("%user% was server unmuted in %channel%"),
+            server_deafened: String::from("%user% was server deafened in %channel%"),
+            server_undeafened: String::from("%user% was server undeafened in %channel%"),
+            self_muted: String::from("%user% muted themselves in %channel%"),
+            self_unmuted: String::from("%user% unmuted themselves in %channel%"),
+            self_deafened: String::from("%user% deafened themselves in %channel%"),
+            self_undeafened: String::from("%user% undeafened themselves in %channel%"),
+            stream_started: String::from("%user% started streaming in %channel%"),
+            stream_stopped: String::from("%user% stopped streaming in %channel%"),
+            video_started: String::from("%user% turned on their camera in %channel%"),
+            video_stopped: String::from("%user% turned off their camera in %channel%"),
+            moderator: String::from("Moderator"),
+            id: String::from("ID"),
+        }
+    }
+}
+
+enum Change {
+    Joined,
+    Left,
+    Moved(ChannelId),
+    ServerMute(bool),
+    ServerDeaf(bool),
+    SelfMute(bool),
+    SelfDeaf(bool),
+    Stream(bool),
+    Video(bool),
+}
+
+impl Change {
+    fn text<'a>(&self, texts: &'a Texts, moderated: bool) -> &'a str {
+        match self {
+            Self::Joined => &texts.joined,
+            Self::Left if moderated => &texts.disconnected,
+            Self::Left => &texts.left,
+            Self::Moved(_) => &texts.moved,
+            Self::ServerMute(true) => &texts.server_muted,
+            Self::ServerMute(false) => &texts.server_unmuted,
+            Self::ServerDeaf(true) => &texts.server_deafened,
+            Self::ServerDeaf(false) => &texts.server_undeafened,
+            Self::SelfMute(true) => &texts.self_muted,
+            Self::SelfMute(false) => &texts.self_unmuted,
+            Self::SelfDeaf(true) => &texts.self_deafened,
+            Self::SelfDeaf(false) => &texts.self_undeafened,
+            Self::Stream(true) => &texts.stream_started,
+            Self::Stream(false) => &texts.stream_stopped,
+            Self::Video(true) => &texts.video_started,
+            Self::Video(false) => &texts.video_stopped,
+        }
+    }
+
+    fn is_moderated(&self) -> bool {
+        matches!(
+            self,
+            Self::Left | Self::Moved(_) | Self::ServerMute(_) | Self::ServerDeaf(_)
+        )
+    }
+}
+
+fn changes(old: Option<&VoiceState>, new: &VoiceState) -> Vec<Change> {
+    match (old.and_then(|old| old.channel_id), new.channel_id) {
+        (None, Some(_)) => return vec![Change::Joined],
+        (Some(_), None) => return vec![Change::Left],
+        (Some(old_channel), Some(new_channel)) if old_channel != new_channel => {
+            return vec![Change::Moved(old_channel)]
+        }
+        (None, None) => return Vec::new(),
+        _ => {}
+    }
+
+    let Some(old) = old else {
+        return Vec::new();
+    };
+
+    let mut changes = Vec::new();
+
+    if old.mute != new.mute {
+        changes.push(Change::ServerMute(new.mute));
+    }
+
+    if old.deaf != new.deaf {
+        changes.push(Change::ServerDeaf(new.deaf));
+    }
+
+    if old.self_mute != new.self_mute {
+        changes.push(Change::SelfMute(new.self_mute));
+    }
+
+    if old.self_deaf != new.self_deaf {
+        changes.push(Change::SelfDeaf(new.self_deaf));
+    }
+
+    let (old_stream, new_stream) = (
+        old.self_stream.unwrap_or_default(),
+        new.self_stream.unwrap_or_default(),
+    );
+
+    if old_stream != new_stream {
+        changes.push(Change::Stream(new_stream));
+    }
+
+    if old.self_video != new.self_video {
+        changes.push(Change::Video(new.self_video));
+    }
+
+    changes
+}
+
+// Looks up the audit log once per update, since server mute and deafen can change together
+#[instrument(skip(ctx, changes, guild_id, user_id))]
+async fn moderator(
+    ctx: &Context,
+    changes: &[Change],
+    guild_id: GuildId,
+    user_id: UserId,
+    channel_id: Option<ChannelId>,
+) -> Option<UserId> {
+    let entry = match changes.iter().find(|change| change.is_moderated())? {
+        Change::Left => {
+            audit::find_counted(
+                ctx,
+                guild_id,
+                Action::Member(MemberAction::MemberDisconnect),
+                |_| true,
+            )
+            .await
+        }
+        Change::Moved(_) => {
+            audit::find_counted(
+                ctx,
+                guild_id,
+                Action::Member(MemberAction::MemberMove),
+                |entry| {
+                    entry
+                        .options
+                        .as_ref()
+                        .and_then(|options| options.channel_id)
+                        == channel_id
+                },
+            )
+            .await
+        }
+        _ => {
+            audit::find_by_target(
+                ctx,
+                guild_id,
+                Action::Member(MemberAction::Update),
+                user_id.get(),
+            )
+            .await
+        }
+    };
+
+    entry.map(|entry| entry.user_id)
+}
+
+#[instrument(skip(ctx, old, new, texts))]
+pub async fn voice_state_update_event(
+    ctx: Context,
+    old: Option<VoiceState>,
+    new: VoiceState,
+    texts: &GlobalTexts,
+) {
+    let Some(guild_id) = new.guild_id else {
+        debug!("Voice state updated outside of a guild.");
+        return;
+    };
+
+    let changes = changes(old.as_ref(), &new);
+
+    if changes.is_empty() {
+        debug!("No logged voice state properties changed.");
+        return;
+    }
+
+    let Ok(Some(destinations)) = channel::get(&ctx, guild_id, Event::VoiceStateUpdate).await else {
+        debug!(
+            "ChannelId of guild '{}' was either None or Err.",
+            guild_id.get()
+        );
+        return;
+    };
+
+    let destinations = ignore::filter(
+        &ctx,
+        guild_id,
+        Event::VoiceStateUpdate,
+        destinations,
+        Subject {
+            channels: [new.channel_id, old.as_ref().and_then(|old| old.channel_id)]
+                .into_iter()
+                .flatten()
+                .collect(),
+            user: Some(new.user_id),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    if destinations.is_empty() {
+        debug!("Event was ignored in guild '{}'.", guild_id.get());
+        return;
+    }
+
+    let texts = texts::for_guild(&ctx, Some(guild_id), texts).await;
+
+    let user = match &new.member {
+        Some(member) => member.user.clone(),
+        None => match new.user_id.to_user(&ctx).await {
+            Ok(user) => user,
+            Err(err) => {
+                debug!("Failed to get user '{}': {err}", new.user_id.get());
+                return;
+            }
+        },
+    };
+
+    if ignore::is_author_ignored(
+        &ctx,
+        guild_id,
+        Event::VoiceStateUpdate,
+        Author {
+            id: user.id,
+            bot: user.bot,
+            webhook_id: None,
+        },
+    )
+    .await
+    {
+        debug!("The user was an ignored bot.");
+        return;
+    }
+
+    let name = match &new.member {
+        Some(member) => member.display_name().to_string(),
+        None => user.display_name().to_string(),
+    };
+
+    let voice_channel = new
+        .channel_id
+        .or(old.as_ref().and_then(|old| old.channel_id));
+
+    let moderator = moderator(&ctx, &changes, guild_id, user.id, new.channel_id).await;
+    let mut embeds = Vec::with_capacity(changes.len());
+
+    for change in &changes {
+        let moderator = moderator.filter(|_| change.is_moderated());
+
+        let mut vars = Vars::new().set("user", user.mention());
+
+        if let Some(channel_id) = voice_channel {
+            vars = vars.set("channel", channel_id.mention());
+        }
+
+        if let Change::Moved(old_channel) = change {
+            vars = vars.set("old_channel", old_channel.mention());
+        }
+
+        let description = template::render(
+            change.text(&texts.voice_state_update, moderator.is_some()),
+            &vars,
+        );
+
+        let mut embed = LogEmbed::default()
+            .color(Colors::PRIMARY)
+            .author(&name, user.avatar_url())
+            .description(description);
+
+        if let Some(moderator) = moderator {
+            embed = embed.field(
+                "moderator",
+                &texts.voice_state_update.moderator,
+                moderator.mention().to_string(),
+                true,
+            );
+        }
+
+        embeds.push(embed.field(
+            "id",
+            &texts.voice_state_update.id,
+            format!("@{} ({})", user.name, user.id),
+            true,
+        ));
+    }
+
+    delivery::send(
+        &ctx,
+        guild_id,
+        Event::VoiceStateUpdate,
+        &destinations,
+        embeds,
+        Vec::new(),
+    )
+    .await;
+}
@@ -1,10 +1,13 @@
-use serenity::all::{Member, Message, MessageUpdateEvent, User};
+use serenity::all::{
+    GuildChannel, GuildMemberUpdateEvent, Member, Message, MessageUpdateEvent, PartialGuildChannel,
+    Ready, Role, RoleId, User, VoiceState,
+};
 use serenity::{
     all::{ChannelId, GuildId, MessageId},
     client::{Context, EventHandler},
 };
 
-use crate::{event, texts::Texts};
+use crate::{data::Data, event, store, texts::Texts};
 
 pub struct Handler {
     texts: Texts,
@@ -18,6 +21,17 @@ impl Handler {
 
 #[serenity::async_trait]
 impl EventHandler for Handler {
+    async fn ready(&self, ctx: Context, _: Ready) {
+        let data = ctx.data.read().await;
+        let data = data.get::<Data>().expect("Data should never be none.");
+
+        data.queue.restore(&ctx);
+    }
+
+    async fn message(&self, ctx: Context, new_message: Message) {
+        store::record(&ctx, &new_message).await
+    }
+
     async fn message_delete(
         &self,
         ctx: Context,
@@ -35,6 +49,23 @@ impl EventHandler for Handler {
         .await
     }
 
+    async fn message_delete_bulk(
+        &self,
+        ctx: Context,
+        channel_id: ChannelId,
+        multiple_deleted_messages_ids: Vec<MessageId>,
+        guild_id: Option<GuildId>,
+    ) {
+        event::message_delete_bulk::message_delete_bulk_event(
+            ctx,
+            channel_id,
+            multiple_deleted_messages_ids,
+            guild_id,
+            &self.texts,
+        )
+        .await
+    }
+
     async fn message_update(
         &self,
         ctx: Context,
@@ -67,4 +98,105 @@ impl EventHandler for Handler {
         )
         .await
     }
+
+    async fn guild_ban_addition(&self, ctx: Context, guild_id: GuildId, banned_user: User) {
+        event::guild_ban_addition::guild_ban_addition_event(ctx, guild_id, banned_user, &self.texts)
+            .await
+    }
+
+    async fn guild_ban_removal(&self, ctx: Context, guild_id: GuildId, unbanned_user: User) {
+        event::guild_ban_removal::guild_ban_removal_event(ctx, guild_id, unbanned_user, &self.texts)
+            .await
+    }
+
+    async fn guild_member_update(
+        &self,
+        ctx: Context,
+        old_if_available: Option<Member>,
+        new: Option<Member>,
+        event: GuildMemberUpdateEvent,
+    ) {
+        event::guild_member_update::guild_member_update_event(
+            ctx,
+            old_if_available,
+            new,
+            event,
+            &self.texts,
+        )
+        .await
+    }
+
+    async fn voice_state_update(&self, ctx: Context, old: Option<VoiceState>, new: VoiceState) {
+        event::voice_state_update::voice_state_update_event(ctx, old, new, &self.texts).await
+    }
+
+    async fn channel_create(&self, ctx: Context, channel: GuildChannel) {
+        event::channel_create::channel_create_event(ctx, channel, &self.texts).await
+    }
+
+    async fn channel_delete(
+        &self,
+        ctx: Context,
+        channel: GuildChannel,
+        _messages: Option<Vec<Message>>,
+    ) {
+        event::channel_delete::channel_delete_event(ctx, channel, &self.texts).await
+    }
+
+    async fn channel_update(&self, ctx: Context, old: Option<GuildChannel>, new: GuildChannel) {
+        event::channel_update::channel_update_event(ctx, old, new, &self.texts).await
+    }
+
+    async fn thread_create(&self, ctx: Context, thread: GuildChannel) {
+        event::thread_create::thread_create_event(ctx, thread, &self.texts).await
+    }
+
+    async fn thread_delete(
+        &self,
+        ctx: Context,
+        thread: PartialGuildChannel,
+        full_thread_data: Option<GuildChannel>,
+    ) {
+        event::thread_delete::thread_delete_event(ctx, thread, full_thread_data, &self.texts).await
+    }
+
+    async fn thread_update(&self, ctx: Context, old: Option<GuildChannel>, new: GuildChannel) {
+        event::thread_update::thread_update_event(ctx, old, new, &self.texts).await
+    }
+
+    async fn guild_role_create(&self, ctx: Context, new: Role) {
+        event::guild_role_create::guild_role_create_event(ctx, new, &self.texts).await
+    }
+
+    async fn guild_role_delete(
+        &self,
+        ctx: Context,
+        guild_id: GuildId,
+        removed_role_id: RoleId,
+        removed_role_data_if_available: Option<Role>,
+    ) {
+        event::guild_role_delete::guild_role_delete_event(
+            ctx,
+            guild_id,
+            removed_role_id,
+            removed_role_data_if_available,
+            &self.texts,
+        )
+        .await
+    }
+
+    async fn guild_role_update(
+        &self,
+        ctx: Context,
+        old_data_if_available: Option<Role>,
+        new: Role,
+    ) {
+        event::guild_role_update::guild_role_update_event(
+            ctx,
+            old_data_if_available,
+            new,
+            &self.texts,
+        )
+        .await
+    }
 }
@@ -0,0 +1,163 @@
+use serenity::{
+    all::{ChannelId, GuildId, RoleId, UserId},
+    client::Context,
+};
+use tracing::{error, instrument};
+
+use crate::{
+    config::{Destination, Exclusions, GuildConfig},
+    data::Data,
+    event::Event,
+};
+
+pub const ALL_EVENTS_KEY: &str = "*";
+
+#[derive(Debug, Default)]
+pub struct Subject {
+    pub channels: Vec<ChannelId>,
+    pub user: Option<UserId>,
+    pub roles: Vec<RoleId>,
+}
+
+#[derive(Debug)]
+pub struct Author {
+    pub id: UserId,
+    pub bot: bool,
+    pub webhook_id: Option<u64>,
+}
+
+#[instrument(skip(ctx, config))]
+pub fn is_author_excluded(
+    ctx: &Context,
+    config: &GuildConfig,
+    event: Event,
+    author: &Author,
+) -> bool {
+    if author.id == ctx.cache.current_user().id {
+        return true;
+    }
+
+    if author
+        .webhook_id
+        .is_some_and(|webhook_id| config.is_own_webhook(webhook_id))
+    {
+        return true;
+    }
+
+    (author.bot || author.webhook_id.is_some()) && !config.includes_bots(event.key())
+}
+
+#[instrument(skip(ctx))]
+pub async fn is_author_ignored(
+    ctx: &Context,
+    guild_id: GuildId,
+    event: Event,
+    author: Author,
+) -> bool {
+    let data = ctx.data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    match data.cache.get_or_insert(guild_id.get()).await {
+        Ok(config) => is_author_excluded(ctx, &config, event, &author),
+        Err(err) => {
+            error!("Failed to get GuildConfig: {err:?}");
+            author.bot || author.webhook_id.is_some()
+        }
+    }
+}
+
+fn matches(
+    exclusions: &Exclusions,
+    channels: &[ChannelId],
+    user: Option<UserId>,
+    roles: &[RoleId],
+) -> bool {
+    channels
+        .iter()
+        .any(|channel| exclusions.channels.contains(&channel.get()))
+        || user.is_some_and(|user| exclusions.users.contains(&user.get()))
+        || roles
+            .iter()
+            .any(|role| exclusions.roles.contains(&role.get()))
+}
+
+#[instrument(skip(ctx, destinations))]
+pub async fn filter(
+    ctx: &Context,
+    guild_id: GuildId,
+    event: Event,
+    destinations: Vec<Destination>,
+    subject: Subject,
+) -> Vec<u64> {
+    let data = ctx.data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let config = match data.cache.get_or_insert(guild_id.get()).await {
+        Ok(config) => config,
+        Err(err) => {
+            error!("Failed to get GuildConfig: {err:?}");
+            return destinations
+                .iter()
+                .map(|destination| destination.channel_id)
+                .collect();
+        }
+    };
+
+    let exclusions = [
+        config.get_exclusions(ALL_EVENTS_KEY),
+        config.get_exclusions(event.key()),
+    ];
+
+    if exclusions.iter().all(Option::is_none)
+        && destinations
+            .iter()
+            .all(|destination| destination.exclusions.is_empty())
+    {
+        return destinations
+            .iter()
+            .map(|destination| destination.channel_id)
+            .collect();
+    }
+
+    let Subject {
+        mut channels,
+        user,
+        mut roles,
+    } = subject;
+
+    if let Some(guild) = guild_id.to_guild_cached(ctx) {
+        let mut i = 0;
+
+        while i < channels.len() {
+            let parent_id = guild
+                .channels
+                .get(&channels[i])
+                .or_else(|| guild.threads.iter().find(|thread| thread.id == channels[i]))
+                .and_then(|channel| channel.parent_id);
+
+            if let Some(parent_id) = parent_id.filter(|id| !channels.contains(id)) {
+                channels.push(parent_id);
+            }
+
+            i += 1;
+        }
+
+        if let Some(member) = user.and_then(|user| guild.members.get(&user)) {
+            roles.extend(member.roles.iter().copied());
+        }
+    }
+
+    if exclusions
+        .into_iter()
+        .flatten()
+        .any(|exclusions| matches(exclusions, &channels, user, &roles))
+    {
+        return Vec::new();
+    }
+
+    destinations
+        .iter()
+        .filter(|destination| !matches(&destination.exclusions, &channels, user, &roles))
+        .map(|destination| destination.channel_id)
+        .collect()
+}
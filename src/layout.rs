@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{CreateEmbedAuthor, CreateEmbedFooter, Timestamp},
+    builder::CreateEmbed,
+};
+
+use self::error::Error;
+use crate::event::Event;
+
+pub mod error;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Icon {
+    #[default]
+    Default,
+    Thumbnail,
+    Author,
+    None,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldLayout {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inline: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Layout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<FieldLayout>>,
+    pub icon: Icon,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+    pub timestamp: bool,
+}
+
+impl Layout {
+    pub fn parse(json: &str, event: Event) -> Result<Layout, Error> {
+        let layout: Layout = serde_json::from_str(json).map_err(Error::Json)?;
+        layout.validate(event)?;
+
+        Ok(layout)
+    }
+
+    pub fn validate(&self, event: Event) -> Result<(), Error> {
+        if let Some(color) = &self.color {
+            parse_color(color).ok_or_else(|| Error::InvalidColor(color.clone()))?;
+        }
+
+        for field in self.fields.iter().flatten() {
+            if !event.fields().contains(&field.id.as_str()) {
+                return Err(Error::UnknownField(field.id.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_color(color: &str) -> Option<u32> {
+    let hex = color.strip_prefix('#').unwrap_or(color);
+
+    if hex.len() != 6 {
+        return None;
+    }
+
+    u32::from_str_radix(hex, 16).ok()
+}
+
+struct LogField {
+    id: &'static str,
+    name: String,
+    value: String,
+    inline: bool,
+}
+
+#[derive(Default)]
+pub struct LogEmbed {
+    color: u32,
+    title: Option<String>,
+    description: Option<String>,
+    author: Option<String>,
+    icon: Option<String>,
+    placement: Icon,
+    image: Option<String>,
+    fields: Vec<LogField>,
+    footer: Option<String>,
+}
+
+impl LogEmbed {
+    pub fn color(mut self, color: u32) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn author(mut self, name: impl Into<String>, icon_url: Option<String>) -> Self {
+        self.author = Some(name.into());
+
+        if icon_url.is_some() {
+            self.icon = icon_url;
+            self.placement = Icon::Author;
+        }
+
+        self
+    }
+
+    pub fn thumbnail(mut self, url: impl Into<String>) -> Self {
+        self.icon = Some(url.into());
+        self.placement = Icon::Thumbnail;
+        self
+    }
+
+    pub fn image(mut self, url: impl Into<String>) -> Self {
+        self.image = Some(url.into());
+        self
+    }
+
+    pub fn field(
+        mut self,
+        id: &'static str,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        inline: bool,
+    ) -> Self {
+        self.fields.push(LogField {
+            id,
+            name: name.into(),
+            value: value.into(),
+            inline,
+        });
+        self
+    }
+
+    pub fn fields(
+        mut self,
+        fields: impl IntoIterator<Item = (&'static str, String, String, bool)>,
+    ) -> Self {
+        for (id, name, value, inline) in fields {
+            self = self.field(id, name, value, inline);
+        }
+        self
+    }
+
+    pub fn footer(mut self, text: impl Into<String>) -> Self {
+        self.footer = Some(text.into());
+        self
+    }
+
+    pub fn build(self, layout: Option<&Layout>) -> CreateEmbed {
+        let default = Layout::default();
+        let layout = layout.unwrap_or(&default);
+
+        let color = layout
+            .color
+            .as_deref()
+            .and_then(parse_color)
+            .unwrap_or(self.color);
+
+        let mut embed = CreateEmbed::default().color(color);
+
+        if let Some(title) = &self.title {
+            embed = embed.title(title);
+        }
+
+        if let Some(description) = self.description {
+            embed = embed.description(description);
+        }
+
+        let placement = match layout.icon {
+            Icon::Default => self.placement,
+            icon => icon,
+        };
+
+        let author_name = self.author.clone().or_else(|| self.title.clone());
+
+        match (placement, self.icon, author_name) {
+            (Icon::Thumbnail, Some(icon), _) | (Icon::Author, Some(icon), None) => {
+                embed = embed.thumbnail(icon);
+
+                if let Some(author) = self.author {
+                    embed = embed.author(CreateEmbedAuthor::new(author));
+                }
+            }
+            (Icon::Author, Some(icon), Some(author)) => {
+                embed = embed.author(CreateEmbedAuthor::new(author).icon_url(icon));
+            }
+            _ => {
+                if let Some(author) = self.author {
+                    embed = embed.author(CreateEmbedAuthor::new(author));
+                }
+            }
+        }
+
+        if let Some(image) = self.image {
+            embed = embed.image(image);
+        }
+
+        let fields = match &layout.fields {
+            Some(order) => order
+                .iter()
+                .flat_map(|field_layout| {
+                    self.fields
+                        .iter()
+                        .filter(|field| field.id == field_layout.id)
+                        .map(|field| {
+                            (
+                                field.name.clone(),
+                                field.value.clone(),
+                                field_layout.inline.unwrap_or(field.inline),
+                            )
+                        })
+                })
+                .collect::<Vec<_>>(),
+            None => self
+                .fields
+                .into_iter()
+                .map(|field| (field.name, field.value, field.inline))
+                .collect(),
+        };
+
+        embed = embed.fields(fields);
+
+        match layout.footer.as_deref().or(self.footer.as_deref()) {
+            Some("") | None => {}
+            Some(footer) => embed = embed.footer(CreateEmbedFooter::new(footer)),
+        }
+
+        if layout.timestamp {
+            embed = embed.timestamp(Timestamp::now());
+        }
+
+        embed
+    }
+}
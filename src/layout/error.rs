@@ -0,0 +1,18 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Json(serde_json::Error),
+    InvalidColor(String),
+    UnknownField(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "{err}"),
+            Self::InvalidColor(color) => write!(f, "invalid colour '{color}'"),
+            Self::UnknownField(id) => write!(f, "unknown field '{id}'"),
+        }
+    }
+}
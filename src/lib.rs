@@ -4,19 +4,29 @@ use poise::{samples::register_globally, Framework, FrameworkOptions};
 use serenity::{all::GatewayIntents, cache::Settings, Client};
 use tracing::{error, info, instrument};
 
-use command::{set, unset};
+use command::{
+    attachments, bots, config::config, diff, format, ignore::ignore, language, layout::layout,
+    logs::logs, manager, retention, set, setup::setup, text::text, unset, webhook::webhook,
+};
 use data::Data;
 use handler::Handler;
 
+mod audit;
 mod cache;
 mod channel;
 mod colors;
 mod command;
 mod config;
 mod data;
+mod delivery;
 mod event;
 mod handler;
+mod ignore;
+mod layout;
+mod locale;
 mod messages;
+mod store;
+mod template;
 mod texts;
 mod utils;
 
@@ -37,9 +47,30 @@ pub async fn init() {
         | GatewayIntents::MESSAGE_CONTENT
         | GatewayIntents::GUILD_MEMBERS;
 
+    let mut commands = vec![
+        setup(),
+        set(),
+        unset(),
+        retention(),
+        diff(),
+        attachments(),
+        bots(),
+        format(),
+        ignore(),
+        text(),
+        layout(),
+        language(),
+        webhook(),
+        config(),
+        manager(),
+        logs(),
+    ];
+
+    locale::localize_commands(&mut commands);
+
     let framework = Framework::<(), Box<dyn std::error::Error + Send + Sync>>::builder()
         .options(FrameworkOptions {
-            commands: vec![set(), unset()],
+            commands,
             ..Default::default()
         })
         .setup(|ctx, _, framework| {
@@ -89,6 +120,11 @@ pub async fn init() {
 
     let shard_manager = Arc::downgrade(&client.shard_manager);
     let cloned_data = data.clone();
+    let store_data = data.clone();
+
+    tokio::spawn(async move {
+        store_data.store.maintain(&store_data.cache).await;
+    });
 
     tokio::spawn(async move {
         loop {
@@ -100,6 +136,9 @@ pub async fn init() {
             if input.trim() == "stop" {
                 cloned_data.cache.invalidate_all();
                 cloned_data.cache.run_pending_tasks().await;
+                cloned_data.store.invalidate_all();
+                cloned_data.store.run_pending_tasks().await;
+                cloned_data.queue.persist_all();
                 let _ = Data::save_texts(&cloned_data.texts);
                 shard_manager.upgrade().unwrap().shutdown_all().await;
                 break;
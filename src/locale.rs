@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tracing::{error, instrument};
+
+use crate::{data, texts::Texts, Error};
+
+const SPANISH_TEXTS: &str = include_str!("../locales/es.json");
+const COMMANDS: &str = include_str!("../locales/commands.json");
+
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize, poise::ChoiceParameter,
+)]
+pub enum Locale {
+    #[default]
+    #[name = "English"]
+    #[serde(rename = "en")]
+    English,
+    #[name = "Español"]
+    #[serde(rename = "es")]
+    Spanish,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Self::English, Self::Spanish];
+
+    pub fn from_discord(locale: &str) -> Option<Locale> {
+        Self::ALL
+            .into_iter()
+            .find(|candidate| candidate.discord_locales().contains(&locale))
+    }
+
+    fn discord_locales(self) -> &'static [&'static str] {
+        match self {
+            Self::English => &["en-US", "en-GB"],
+            Self::Spanish => &["es-ES", "es-419"],
+        }
+    }
+
+    fn bundle(self) -> Option<&'static str> {
+        match self {
+            Self::English => None,
+            Self::Spanish => Some(SPANISH_TEXTS),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ParameterTexts {
+    name: Option<String>,
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct CommandTexts {
+    name: Option<String>,
+    description: String,
+    #[serde(default)]
+    parameters: HashMap<String, ParameterTexts>,
+}
+
+#[derive(Deserialize)]
+struct CommandBundle {
+    commands: HashMap<String, CommandTexts>,
+    #[serde(default)]
+    choices: HashMap<String, String>,
+}
+
+#[instrument]
+pub fn load_translations() -> Result<HashMap<Locale, Texts>, data::error::Error> {
+    let mut translations = HashMap::new();
+
+    for locale in Locale::ALL {
+        let Some(bundle) = locale.bundle() else {
+            continue;
+        };
+
+        let texts: Texts = match serde_json::from_str(bundle) {
+            Ok(texts) => texts,
+            Err(err) => {
+                error!("Failed to deserialize {locale:?} texts from JSON: {err}");
+                return Err(data::error::Error::Deserialization(err));
+            }
+        };
+
+        if let Err(errors) = texts.validate() {
+            for (key, err) in &errors {
+                error!("Invalid {locale:?} text '{key}': {err}");
+            }
+
+            return Err(data::error::Error::Template(errors));
+        }
+
+        translations.insert(locale, texts);
+    }
+
+    Ok(translations)
+}
+
+#[instrument(skip(commands))]
+pub fn localize_commands(commands: &mut [poise::Command<(), Error>]) {
+    let bundles: HashMap<Locale, CommandBundle> = match serde_json::from_str(COMMANDS) {
+        Ok(bundles) => bundles,
+        Err(err) => {
+            error!("Failed to deserialize command localisations from JSON: {err}");
+            return;
+        }
+    };
+
+    for (locale, bundle) in &bundles {
+        localize(commands, "", *locale, bundle);
+    }
+}
+
+fn localize(
+    commands: &mut [poise::Command<(), Error>],
+    parent: &str,
+    locale: Locale,
+    bundle: &CommandBundle,
+) {
+    for command in commands {
+        let qualified_name = if parent.is_empty() {
+            command.name.clone()
+        } else {
+            format!("{parent} {}", command.name)
+        };
+
+        if let Some(texts) = bundle.commands.get(&qualified_name) {
+            for discord_locale in locale.discord_locales() {
+                if let Some(name) = &texts.name {
+                    command
+                        .name_localizations
+                        .insert(discord_locale.to_string(), name.clone());
+                }
+
+                command
+                    .description_localizations
+                    .insert(discord_locale.to_string(), texts.description.clone());
+            }
+
+            if locale == Locale::default() {
+                command.description = Some(texts.description.clone());
+            }
+
+            for parameter in &mut command.parameters {
+                let Some(parameter_texts) = texts.parameters.get(&parameter.name) else {
+                    continue;
+                };
+
+                for discord_locale in locale.discord_locales() {
+                    if let Some(name) = &parameter_texts.name {
+                        parameter
+                            .name_localizations
+                            .insert(discord_locale.to_string(), name.clone());
+                    }
+
+                    parameter.description_localizations.insert(
+                        discord_locale.to_string(),
+                        parameter_texts.description.clone(),
+                    );
+                }
+
+                if locale == Locale::default() {
+                    parameter.description = Some(parameter_texts.description.clone());
+                }
+            }
+        }
+
+        for parameter in &mut command.parameters {
+            for choice in &mut parameter.choices {
+                let Some(name) = bundle.choices.get(&choice.name) else {
+                    continue;
+                };
+
+                for discord_locale in locale.discord_locales() {
+                    choice
+                        .localizations
+                        .insert(discord_locale.to_string(), name.clone());
+                }
+            }
+        }
+
+        localize(&mut command.subcommands, &qualified_name, locale, bundle);
+    }
+}
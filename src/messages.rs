@@ -1,8 +1,16 @@
+use std::borrow::Cow;
+
 use poise::CreateReply;
-use serenity::builder::CreateEmbed;
+use serenity::{all::CreateAttachment, builder::CreateEmbed};
 use tracing::{error, instrument};
 
-use crate::{colors::Colors, data::Data, Context};
+use crate::{
+    colors::Colors,
+    data::Data,
+    template::{self, Vars},
+    texts::{self, Texts},
+    Context,
+};
 
 #[instrument(skip(ctx))]
 pub async fn send_ephemeral_message(ctx: &Context<'_>, color: u32, title: &str, description: &str) {
@@ -21,78 +29,706 @@ pub async fn send_ephemeral_message(ctx: &Context<'_>, color: u32, title: &str,
     }
 }
 
+pub async fn guild_texts<'a>(ctx: &Context<'_>, data: &'a Data) -> Cow<'a, Texts> {
+    texts::for_interaction(
+        ctx.serenity_context(),
+        ctx.guild_id(),
+        ctx.locale(),
+        &data.texts,
+    )
+    .await
+}
+
 pub trait Messages {
     async fn not_in_guild(&self, data: &Data);
     async fn internal_error(&self, data: &Data);
     async fn not_valid_channel(&self, data: &Data);
     async fn channel_set(&self, data: &Data);
     async fn channel_unset(&self, data: &Data);
-    async fn missing_administrator(&self, data: &Data);
+    async fn missing_manager_permission(&self, data: &Data);
+    async fn retention_set(&self, data: &Data, days: u64);
+    async fn webhooks_enabled(&self, data: &Data);
+    async fn webhooks_disabled(&self, data: &Data);
+    async fn webhook_identity_set(&self, data: &Data);
+    async fn webhook_identity_reset(&self, data: &Data);
+    async fn diff_mode_set(&self, data: &Data);
+    async fn attachment_capture_enabled(&self, data: &Data);
+    async fn attachment_capture_disabled(&self, data: &Data);
+    async fn ignore_target_missing(&self, data: &Data);
+    async fn ignore_added(&self, data: &Data);
+    async fn ignore_removed(&self, data: &Data);
+    async fn ignore_list_empty(&self, data: &Data);
+    async fn ignore_list(&self, data: &Data, list: &str);
+    async fn bots_included(&self, data: &Data);
+    async fn bots_excluded(&self, data: &Data);
+    async fn text_unknown_key(&self, data: &Data);
+    async fn text_set(&self, data: &Data);
+    async fn text_reset(&self, data: &Data);
+    async fn text_preview(&self, data: &Data, key: &str, text: &str);
+    async fn language_set(&self, data: &Data);
+    async fn language_reset(&self, data: &Data);
+    async fn text_invalid(&self, data: &Data, error: &str);
+    async fn layout_set(&self, data: &Data);
+    async fn layout_reset(&self, data: &Data);
+    async fn layout_invalid(&self, data: &Data, error: &str);
+    async fn layout_show(&self, data: &Data, layout: &str, fields: &str);
+    async fn format_set(&self, data: &Data);
+    async fn ignore_destination_unknown(&self, data: &Data);
+    async fn config_show(&self, data: &Data, description: &str);
+    async fn config_exported(&self, data: &Data, file: CreateAttachment);
+    async fn config_invalid(&self, data: &Data, error: &str);
+    async fn config_too_large(&self, data: &Data, size: u32);
+    async fn config_imported(&self, data: &Data, skipped: usize);
+    async fn missing_manage_guild_permission(&self, data: &Data);
+    async fn manager_set(&self, data: &Data, role: &str);
+    async fn manager_reset(&self, data: &Data);
+    async fn missing_bot_permissions(&self, data: &Data, permissions: &str);
+    async fn doctor_ok(&self, data: &Data);
+    async fn doctor_problems(&self, data: &Data, problems: &str);
+    async fn alerts_set(&self, data: &Data, target: &str, disable: &str);
+    async fn doctor_unavailable(&self, data: &Data);
+    async fn channel_set_unchecked(&self, data: &Data);
 }
 
 impl Messages for Context<'_> {
     #[instrument(skip(self, data))]
     async fn not_in_guild(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
         send_ephemeral_message(
             self,
             Colors::ERROR,
-            data.texts.error_embed_title(),
-            data.texts.error_command_executed_in_dm(),
+            texts.error_embed_title(),
+            texts.error_command_executed_in_dm(),
         )
         .await;
     }
 
     #[instrument(skip(self, data))]
     async fn internal_error(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
         send_ephemeral_message(
             self,
             Colors::ERROR,
-            data.texts.error_embed_title(),
-            data.texts.internal_error(),
+            texts.error_embed_title(),
+            texts.internal_error(),
         )
         .await;
     }
 
     #[instrument(skip(self, data))]
     async fn not_valid_channel(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
         send_ephemeral_message(
             self,
             Colors::ERROR,
-            data.texts.error_embed_title(),
-            data.texts.not_valid_channel(),
+            texts.error_embed_title(),
+            texts.not_valid_channel(),
         )
         .await;
     }
 
     #[instrument(skip(self, data))]
     async fn channel_set(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
         send_ephemeral_message(
             self,
             Colors::PRIMARY,
-            data.texts.success_embed_title(),
-            data.texts.channel_set(),
+            texts.success_embed_title(),
+            texts.channel_set(),
         )
         .await;
     }
 
     #[instrument(skip(self, data))]
     async fn channel_unset(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.channel_unset(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn missing_manager_permission(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            texts.missing_manager_permission(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn retention_set(&self, data: &Data, days: u64) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            &template::render(texts.retention_set(), &Vars::new().set("days", days)),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn webhooks_enabled(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.webhooks_enabled(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn webhooks_disabled(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.webhooks_disabled(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn webhook_identity_set(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.webhook_identity_set(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn webhook_identity_reset(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.webhook_identity_reset(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn diff_mode_set(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.diff_mode_set(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn attachment_capture_enabled(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.attachment_capture_enabled(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn attachment_capture_disabled(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.attachment_capture_disabled(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn ignore_target_missing(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            texts.ignore_target_missing(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn ignore_added(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.ignore_added(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn ignore_removed(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
         send_ephemeral_message(
             self,
             Colors::PRIMARY,
-            data.texts.success_embed_title(),
-            data.texts.channel_unset(),
+            texts.success_embed_title(),
+            texts.ignore_removed(),
         )
         .await;
     }
 
     #[instrument(skip(self, data))]
-    async fn missing_administrator(&self, data: &Data) {
+    async fn ignore_list_empty(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.ignore_list_empty(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data, list))]
+    async fn ignore_list(&self, data: &Data, list: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(self, Colors::PRIMARY, texts.ignore_list_title(), list).await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn bots_included(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.bots_included(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn bots_excluded(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.bots_excluded(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn text_unknown_key(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
         send_ephemeral_message(
             self,
             Colors::ERROR,
-            data.texts.error_embed_title(),
-            data.texts.missing_administrator_permission(),
+            texts.error_embed_title(),
+            texts.text_unknown_key(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn text_set(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.text_set(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn text_reset(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.text_reset(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data, text))]
+    async fn text_preview(&self, data: &Data, key: &str, text: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            key,
+            &template::render(texts.text_preview(), &Vars::new().set("text", text)),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn language_set(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.language_set(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn language_reset(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.language_reset(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn text_invalid(&self, data: &Data, error: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            &template::render(texts.text_invalid(), &Vars::new().set("error", error)),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn layout_set(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.layout_set(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn layout_reset(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.layout_reset(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn layout_invalid(&self, data: &Data, error: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            &template::render(texts.layout_invalid(), &Vars::new().set("error", error)),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data, layout))]
+    async fn layout_show(&self, data: &Data, layout: &str, fields: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            &template::render(
+                texts.layout_show(),
+                &Vars::new().set("layout", layout).set("fields", fields),
+            ),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn format_set(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.format_set(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn ignore_destination_unknown(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            texts.ignore_destination_unknown(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data, description))]
+    async fn config_show(&self, data: &Data, description: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(self, Colors::PRIMARY, texts.config_show(), description).await;
+    }
+
+    #[instrument(skip(self, data, file))]
+    async fn config_exported(&self, data: &Data, file: CreateAttachment) {
+        let texts = guild_texts(self, data).await;
+
+        if let Err(err) = self
+            .send(
+                CreateReply::default()
+                    .ephemeral(true)
+                    .embed(
+                        CreateEmbed::default()
+                            .color(Colors::PRIMARY)
+                            .title(texts.success_embed_title())
+                            .description(texts.config_exported()),
+                    )
+                    .attachment(file),
+            )
+            .await
+        {
+            error!("Failed to send message: {err}");
+        }
+    }
+
+    #[instrument(skip(self, data))]
+    async fn config_invalid(&self, data: &Data, error: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            &template::render(texts.config_invalid(), &Vars::new().set("error", error)),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn config_too_large(&self, data: &Data, size: u32) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            &template::render(texts.config_too_large(), &Vars::new().set("size", size)),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn config_imported(&self, data: &Data, skipped: usize) {
+        let texts = guild_texts(self, data).await;
+
+        let description = if skipped == 0 {
+            texts.config_imported().to_string()
+        } else {
+            template::render(
+                texts.config_imported_skipped(),
+                &Vars::new().set("count", skipped.to_string()),
+            )
+        };
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            &description,
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn missing_manage_guild_permission(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            texts.missing_manage_guild_permission(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn manager_set(&self, data: &Data, role: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            &template::render(texts.manager_set(), &Vars::new().set("role", role)),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn manager_reset(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.manager_reset(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn missing_bot_permissions(&self, data: &Data, permissions: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            &template::render(
+                texts.missing_bot_permissions(),
+                &Vars::new().set("permissions", permissions),
+            ),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn doctor_ok(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.doctor_ok(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data, problems))]
+    async fn doctor_problems(&self, data: &Data, problems: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            &template::render(
+                texts.doctor_problems(),
+                &Vars::new().set("problems", problems),
+            ),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn alerts_set(&self, data: &Data, target: &str, disable: &str) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            &template::render(
+                texts.alerts_set(),
+                &Vars::new().set("target", target).set("disable", disable),
+            ),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn doctor_unavailable(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::ERROR,
+            texts.error_embed_title(),
+            texts.doctor_unavailable(),
+        )
+        .await;
+    }
+
+    #[instrument(skip(self, data))]
+    async fn channel_set_unchecked(&self, data: &Data) {
+        let texts = guild_texts(self, data).await;
+
+        send_ephemeral_message(
+            self,
+            Colors::PRIMARY,
+            texts.success_embed_title(),
+            texts.channel_set_unchecked(),
         )
         .await;
     }
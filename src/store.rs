@@ -0,0 +1,685 @@
+use std::{
+    collections::BTreeMap,
+    env::current_dir,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use moka::{
+    future::{Cache, FutureExt},
+    notification::{ListenerFuture, RemovalCause},
+};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{Attachment, CreateAttachment, Message, MessageId},
+    client::Context,
+};
+use tokio::{task, time::interval};
+use tracing::{debug, error, info, instrument};
+
+use self::error::Error;
+use crate::{
+    cache::GuildConfigCache,
+    data::Data,
+    event::Event,
+    ignore::{is_author_excluded, Author},
+};
+
+pub mod error;
+
+const STORE_DURATION_IN_SECONDS: u64 = 20 * 60;
+const FLUSH_INTERVAL_IN_SECONDS: u64 = 60;
+const PRUNE_INTERVAL_IN_SECONDS: u64 = 60 * 60;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+const MAX_CAPTURED_ATTACHMENT_SIZE_IN_BYTES: u32 = 8 * 1024 * 1024;
+const MAX_REUPLOADED_SIZE_IN_BYTES: usize = 8 * 1024 * 1024;
+const MAX_REUPLOADED_ATTACHMENTS: usize = 9;
+
+#[instrument]
+fn get_store_directory() -> PathBuf {
+    let mut path = PathBuf::new();
+    path.push(current_dir().expect("The current directory could not be obtained."));
+    path.push("messages");
+
+    path
+}
+
+#[instrument]
+fn get_store_path(channel_id: u64) -> PathBuf {
+    let mut path = get_store_directory();
+    path.push(format!("{channel_id}.json"));
+
+    path
+}
+
+#[instrument]
+fn get_attachment_directory() -> PathBuf {
+    let mut path = PathBuf::new();
+    path.push(current_dir().expect("The current directory could not be obtained."));
+    path.push("attachments");
+
+    path
+}
+
+#[instrument]
+fn get_attachment_path(attachment_id: u64) -> PathBuf {
+    let mut path = get_attachment_directory();
+    path.push(attachment_id.to_string());
+
+    path
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StoredAttachment {
+    #[serde(rename = "i")]
+    pub id: u64,
+    #[serde(rename = "n")]
+    pub filename: String,
+    #[serde(rename = "s")]
+    pub size: u32,
+    #[serde(rename = "u")]
+    pub url: String,
+}
+
+impl From<&Attachment> for StoredAttachment {
+    fn from(attachment: &Attachment) -> Self {
+        Self {
+            id: attachment.id.get(),
+            filename: attachment.filename.clone(),
+            size: attachment.size,
+            url: attachment.url.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StoredAuthor {
+    #[serde(rename = "i")]
+    pub id: u64,
+    #[serde(rename = "n")]
+    pub name: String,
+    #[serde(rename = "g", default)]
+    pub global_name: Option<String>,
+    #[serde(rename = "k", default)]
+    pub nick: Option<String>,
+    #[serde(rename = "a", default)]
+    pub avatar_url: Option<String>,
+    #[serde(rename = "b", default)]
+    pub bot: bool,
+}
+
+impl StoredAuthor {
+    pub fn display_name(&self) -> &str {
+        self.nick
+            .as_deref()
+            .or(self.global_name.as_deref())
+            .unwrap_or(&self.name)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StoredMessage {
+    #[serde(rename = "i")]
+    pub id: u64,
+    #[serde(rename = "c")]
+    pub channel_id: u64,
+    #[serde(rename = "g")]
+    pub guild_id: u64,
+    #[serde(rename = "a")]
+    pub author: StoredAuthor,
+    #[serde(rename = "t", default)]
+    pub content: String,
+    #[serde(rename = "f", default)]
+    pub attachments: Vec<StoredAttachment>,
+    #[serde(rename = "s", default)]
+    pub stickers: Vec<String>,
+    #[serde(rename = "e", default)]
+    pub embeds: Vec<String>,
+    #[serde(rename = "w", default)]
+    pub webhook_id: Option<u64>,
+}
+
+impl StoredMessage {
+    pub fn created_at(&self) -> i64 {
+        MessageId::new(self.id).created_at().unix_timestamp()
+    }
+}
+
+impl From<&Message> for StoredMessage {
+    fn from(message: &Message) -> Self {
+        Self {
+            id: message.id.get(),
+            channel_id: message.channel_id.get(),
+            guild_id: message.guild_id.map(|id| id.get()).unwrap_or_default(),
+            author: StoredAuthor {
+                id: message.author.id.get(),
+                name: message.author.name.clone(),
+                global_name: message.author.global_name.clone(),
+                nick: message
+                    .member
+                    .as_ref()
+                    .and_then(|member| member.nick.clone()),
+                avatar_url: message.author.avatar_url(),
+                bot: message.author.bot,
+            },
+            content: message.content.clone(),
+            attachments: message
+                .attachments
+                .iter()
+                .map(StoredAttachment::from)
+                .collect(),
+            stickers: message
+                .sticker_items
+                .iter()
+                .map(|sticker| sticker.name.clone())
+                .collect(),
+            embeds: message
+                .embeds
+                .iter()
+                .filter_map(|embed| embed.title.clone().or(embed.url.clone()))
+                .collect(),
+            webhook_id: message.webhook_id.map(|id| id.get()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ChannelMessages {
+    #[serde(rename = "r")]
+    retention: i64,
+    #[serde(rename = "m")]
+    messages: BTreeMap<u64, StoredMessage>,
+
+    #[serde(skip_serializing, skip_deserializing)]
+    has_changed: bool,
+}
+
+impl ChannelMessages {
+    fn prune(&mut self) {
+        let oldest = chrono::Utc::now().timestamp() - self.retention;
+        let count = self.messages.len();
+
+        self.messages.retain(|_, message| {
+            let keep = message.created_at() >= oldest;
+            if !keep {
+                remove_attachments(&message.attachments);
+            }
+
+            keep
+        });
+
+        if self.messages.len() != count {
+            self.has_changed = true;
+        }
+    }
+}
+
+pub struct MessageStore {
+    channels: Cache<u64, Arc<Mutex<ChannelMessages>>>,
+}
+
+impl MessageStore {
+    #[instrument]
+    pub fn new() -> Self {
+        info!(
+            "Creating MessageStore with an idle time of {} seconds.",
+            STORE_DURATION_IN_SECONDS
+        );
+
+        Self {
+            channels: Cache::builder()
+                .time_to_idle(Duration::from_secs(STORE_DURATION_IN_SECONDS))
+                .async_eviction_listener(Self::on_eviction)
+                .build(),
+        }
+    }
+
+    #[instrument(skip(value))]
+    fn on_eviction(
+        key: Arc<u64>,
+        value: Arc<Mutex<ChannelMessages>>,
+        cause: RemovalCause,
+    ) -> ListenerFuture {
+        debug!("Key has evicted.");
+        let id = *key.as_ref();
+        async move {
+            Self::save_in_background(id, value).await;
+        }
+        .boxed()
+    }
+
+    #[instrument(skip(self))]
+    async fn channel(&self, channel_id: u64) -> Arc<Mutex<ChannelMessages>> {
+        self.channels
+            .get_with(channel_id, async move {
+                Arc::new(Mutex::new(Self::load_in_background(channel_id).await))
+            })
+            .await
+    }
+
+    #[instrument(skip(self, message))]
+    pub async fn insert(&self, message: StoredMessage, retention_in_days: u64) {
+        let channel = self.channel(message.channel_id).await;
+        let mut channel = channel
+            .lock()
+            .expect("Store lock should never be poisoned.");
+
+        channel.retention = retention_in_days as i64 * SECONDS_PER_DAY;
+        channel.messages.insert(message.id, message);
+        channel.has_changed = true;
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get(&self, channel_id: u64, message_id: u64) -> Option<StoredMessage> {
+        let channel = self.channel(channel_id).await;
+        let channel = channel
+            .lock()
+            .expect("Store lock should never be poisoned.");
+
+        channel.messages.get(&message_id).cloned()
+    }
+
+    #[instrument(skip(self))]
+    pub async fn remove(&self, channel_id: u64, message_id: u64) -> Option<StoredMessage> {
+        let channel = self.channel(channel_id).await;
+        let mut channel = channel
+            .lock()
+            .expect("Store lock should never be poisoned.");
+
+        let message = channel.messages.remove(&message_id);
+        if message.is_some() {
+            channel.has_changed = true;
+        }
+
+        message
+    }
+
+    #[instrument(skip(self, content))]
+    pub async fn update_content(&self, channel_id: u64, message_id: u64, content: &str) {
+        let channel = self.channel(channel_id).await;
+        let mut channel = channel
+            .lock()
+            .expect("Store lock should never be poisoned.");
+
+        if let Some(message) = channel.messages.get_mut(&message_id) {
+            message.content = content.to_string();
+            channel.has_changed = true;
+        }
+    }
+
+    #[instrument(skip(self, attachments))]
+    pub async fn update_attachments(
+        &self,
+        channel_id: u64,
+        message_id: u64,
+        attachments: Vec<StoredAttachment>,
+    ) {
+        let channel = self.channel(channel_id).await;
+        let mut channel = channel
+            .lock()
+            .expect("Store lock should never be poisoned.");
+
+        if let Some(message) = channel.messages.get_mut(&message_id) {
+            message.attachments = attachments;
+            channel.has_changed = true;
+        }
+    }
+
+    // Messages are pruned with the guild's current retention, not the one set when they were stored
+    #[instrument(skip(cache, channel))]
+    async fn refresh_retention(cache: &GuildConfigCache, channel: &Mutex<ChannelMessages>) {
+        let guild_id = channel
+            .lock()
+            .expect("Store lock should never be poisoned.")
+            .messages
+            .values()
+            .next()
+            .map(|message| message.guild_id);
+
+        let Some(guild_id) = guild_id else {
+            return;
+        };
+
+        match cache.get_or_insert(guild_id).await {
+            Ok(config) => {
+                channel
+                    .lock()
+                    .expect("Store lock should never be poisoned.")
+                    .retention = config.message_retention() as i64 * SECONDS_PER_DAY;
+            }
+            Err(err) => error!("Failed to get GuildConfig: {err:?}"),
+        }
+    }
+
+    #[instrument(skip(self, cache))]
+    pub async fn flush(&self, cache: &GuildConfigCache) {
+        debug!("Flushing MessageStore...");
+        let channels = self.channels.iter().collect::<Vec<_>>();
+
+        for (id, channel) in channels {
+            Self::refresh_retention(cache, &channel).await;
+            Self::save_in_background(*id, channel).await;
+        }
+    }
+
+    #[instrument(skip(self, cache))]
+    pub async fn prune_files(&self, cache: &GuildConfigCache) {
+        debug!("Pruning stored messages...");
+        let ids = task::spawn_blocking(|| {
+            let entries = match fs::read_dir(get_store_directory()) {
+                Ok(entries) => entries,
+                Err(err) => {
+                    debug!("Failed to read store directory: {err}");
+                    return Vec::new();
+                }
+            };
+
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    entry
+                        .path()
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .and_then(|stem| stem.parse::<u64>().ok())
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
+
+        for id in ids {
+            if self.channels.contains_key(&id) {
+                continue;
+            }
+
+            let channel = Arc::new(Mutex::new(Self::load_in_background(id).await));
+            Self::refresh_retention(cache, &channel).await;
+            Self::save_in_background(id, channel).await;
+        }
+    }
+
+    #[instrument(skip(self, cache))]
+    pub async fn maintain(&self, cache: &GuildConfigCache) {
+        let mut flush = interval(Duration::from_secs(FLUSH_INTERVAL_IN_SECONDS));
+        let mut prune = interval(Duration::from_secs(PRUNE_INTERVAL_IN_SECONDS));
+
+        loop {
+            tokio::select! {
+                _ = flush.tick() => self.flush(cache).await,
+                _ = prune.tick() => self.prune_files(cache).await,
+            }
+        }
+    }
+
+    // File I/O runs on the blocking pool so it doesn't stall the gateway event handlers
+    async fn load_in_background(channel_id: u64) -> ChannelMessages {
+        task::spawn_blocking(move || Self::load(channel_id))
+            .await
+            .unwrap_or_default()
+    }
+
+    async fn save_in_background(channel_id: u64, channel: Arc<Mutex<ChannelMessages>>) {
+        if let Err(err) = task::spawn_blocking(move || Self::save(channel_id, &channel)).await {
+            error!("Failed to save stored messages: {err}");
+        }
+    }
+
+    #[instrument]
+    fn load(channel_id: u64) -> ChannelMessages {
+        debug!("Loading stored messages...");
+        let path = get_store_path(channel_id);
+        if !path.exists() {
+            return ChannelMessages::default();
+        }
+
+        let raw_data = match fs::read_to_string(&path) {
+            Ok(raw_data) => raw_data,
+            Err(err) => {
+                error!("Failed to read store file: {err}");
+                return ChannelMessages::default();
+            }
+        };
+
+        match serde_json::from_str(&raw_data) {
+            Ok(messages) => messages,
+            Err(err) => {
+                error!("Failed to deserialize stored messages from JSON: {err}");
+                ChannelMessages::default()
+            }
+        }
+    }
+
+    #[instrument(skip(channel))]
+    fn save(channel_id: u64, channel: &Mutex<ChannelMessages>) -> Result<bool, Error> {
+        let mut channel = channel
+            .lock()
+            .expect("Store lock should never be poisoned.");
+        channel.prune();
+
+        if !channel.has_changed {
+            return Ok(false);
+        }
+
+        debug!("Saving stored messages...");
+        let path = get_store_path(channel_id);
+
+        if channel.messages.is_empty() {
+            if path.exists() {
+                if let Err(err) = fs::remove_file(&path) {
+                    error!("Failed to remove store file: {err}");
+                    return Err(Error::Writing(err));
+                }
+            }
+
+            channel.has_changed = false;
+            return Ok(true);
+        }
+
+        let directory = get_store_directory();
+        if !directory.exists() {
+            if let Err(err) = fs::create_dir(&directory) {
+                error!("Failed to create directory: {err}");
+                return Err(Error::Writing(err));
+            };
+        }
+
+        let json = match serde_json::to_string(&*channel) {
+            Ok(json) => json,
+            Err(err) => {
+                error!("Failed to serialize stored messages to JSON: {err}");
+                return Err(Error::Serialization(err));
+            }
+        };
+
+        match fs::write(path, json) {
+            Ok(_) => {
+                channel.has_changed = false;
+                debug!("Saved successfully.");
+                Ok(true)
+            }
+            Err(err) => {
+                error!("Failed to write store file: {err}");
+                Err(Error::Writing(err))
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub fn invalidate_all(&self) {
+        self.channels.invalidate_all();
+    }
+
+    #[instrument(skip(self))]
+    pub async fn run_pending_tasks(&self) {
+        info!("Running pending store tasks...");
+        self.channels.run_pending_tasks().await;
+        info!("Pending store tasks completed.");
+    }
+}
+
+impl Default for MessageStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[instrument(skip(ctx, message))]
+pub async fn record(ctx: &Context, message: &Message) {
+    let Some(guild_id) = message.guild_id else {
+        debug!("Message sent in DMs.");
+        return;
+    };
+
+    let data = ctx.data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let config = match data.cache.get_or_insert(guild_id.get()).await {
+        Ok(config) => config,
+        Err(err) => {
+            error!("Failed to get GuildConfig: {err:?}");
+            return;
+        }
+    };
+
+    let author = Author {
+        id: message.author.id,
+        bot: message.author.bot,
+        webhook_id: message.webhook_id.map(|id| id.get()),
+    };
+
+    let retention = config.message_retention();
+    let is_logged = Event::MESSAGE_EVENTS.iter().any(|event| {
+        !config.get_destinations(event.key()).is_empty()
+            && !is_author_excluded(ctx, &config, *event, &author)
+    });
+
+    if retention == 0 || !is_logged {
+        debug!("Guild '{}' doesn't store messages.", guild_id.get());
+        return;
+    }
+
+    data.store
+        .insert(StoredMessage::from(message), retention)
+        .await;
+
+    if config.capture_attachments() && !message.attachments.is_empty() {
+        tokio::spawn(capture_attachments(
+            ctx.clone(),
+            message.channel_id.get(),
+            message.id.get(),
+            message.attachments.clone(),
+        ));
+    }
+}
+
+// Downloads run in the background so the message handler isn't held up by them
+#[instrument(skip(ctx, attachments))]
+async fn capture_attachments(
+    ctx: Context,
+    channel_id: u64,
+    message_id: u64,
+    attachments: Vec<Attachment>,
+) {
+    for attachment in attachments {
+        if attachment.size > MAX_CAPTURED_ATTACHMENT_SIZE_IN_BYTES {
+            debug!("Attachment '{}' is too large to capture.", attachment.id);
+            continue;
+        }
+
+        let bytes = match attachment.download().await {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                error!("Failed to download attachment: {err}");
+                continue;
+            }
+        };
+
+        let attachment_id = attachment.id.get();
+        let written = task::spawn_blocking(move || {
+            let directory = get_attachment_directory();
+            if !directory.exists() {
+                if let Err(err) = fs::create_dir(&directory) {
+                    error!("Failed to create directory: {err}");
+                    return false;
+                };
+            }
+
+            if let Err(err) = fs::write(get_attachment_path(attachment_id), bytes) {
+                error!("Failed to write attachment file: {err}");
+                return false;
+            }
+
+            true
+        })
+        .await
+        .unwrap_or_default();
+
+        if !written {
+            continue;
+        }
+
+        let data = ctx.data.read().await;
+        let data = data.get::<Data>().expect("Data should never be none.");
+
+        // The message may have been deleted or pruned while the attachment was downloading
+        if data.store.get(channel_id, message_id).await.is_none() {
+            remove_attachments(&[StoredAttachment::from(&attachment)]);
+        }
+    }
+}
+
+#[instrument(skip(attachments))]
+pub fn take_attachments(attachments: &[StoredAttachment]) -> Vec<CreateAttachment> {
+    let mut files = Vec::new();
+    let mut size = 0;
+
+    for attachment in attachments {
+        let path = get_attachment_path(attachment.id);
+        if !path.exists() {
+            continue;
+        }
+
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(err) => {
+                error!("Failed to read attachment file: {err}");
+                continue;
+            }
+        };
+
+        if let Err(err) = fs::remove_file(&path) {
+            error!("Failed to remove attachment file: {err}");
+        }
+
+        if files.len() == MAX_REUPLOADED_ATTACHMENTS
+            || size + data.len() > MAX_REUPLOADED_SIZE_IN_BYTES
+        {
+            debug!(
+                "Attachment '{}' doesn't fit in the log message.",
+                attachment.id
+            );
+            continue;
+        }
+
+        size += data.len();
+        files.push(CreateAttachment::bytes(data, attachment.filename.clone()));
+    }
+
+    files
+}
+
+#[instrument(skip(attachments))]
+pub fn remove_attachments(attachments: &[StoredAttachment]) {
+    for attachment in attachments {
+        let path = get_attachment_path(attachment.id);
+
+        if path.exists() {
+            if let Err(err) = fs::remove_file(&path) {
+                error!("Failed to remove attachment file: {err}");
+            }
+        }
+    }
+}
@@ -0,0 +1,451 @@
+use std::{collections::HashMap, mem};
+
+use tracing::error;
+
+use self::error::Error;
+use crate::utils::text::truncate;
+
+pub mod error;
+
+const TAG: char = '%';
+const SECTION: char = '?';
+const INVERTED_SECTION: char = '!';
+const SECTION_END: char = '/';
+const FILTER_SEPARATOR: char = '|';
+const ARGUMENT_SEPARATOR: char = ':';
+const DEFAULT_TIMESTAMP_STYLE: &str = "f";
+const TIMESTAMP_STYLES: [&str; 7] = ["t", "T", "d", "D", "f", "F", "R"];
+const ESCAPED_CHARACTERS: [char; 11] = ['\\', '*', '_', '~', '`', '|', '>', '#', '[', ']', '<'];
+
+pub type Variables = &'static [(&'static str, &'static [&'static str])];
+
+enum Filter {
+    Raw,
+    Escape,
+    Upper,
+    Lower,
+    Code,
+    Truncate(usize),
+    Time(String),
+}
+
+enum Node {
+    Text(String),
+    Variable {
+        name: String,
+        filters: Vec<Filter>,
+    },
+    Section {
+        name: String,
+        inverted: bool,
+        children: Vec<Node>,
+    },
+}
+
+struct Frame {
+    section: Option<(String, bool)>,
+    nodes: Vec<Node>,
+}
+
+struct Value {
+    text: String,
+    user: bool,
+}
+
+#[derive(Default)]
+pub struct Vars {
+    values: HashMap<&'static str, Value>,
+}
+
+impl Vars {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.values.insert(
+            name,
+            Value {
+                text: value.to_string(),
+                user: false,
+            },
+        );
+        self
+    }
+
+    pub fn user(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.values.insert(
+            name,
+            Value {
+                text: value.to_string(),
+                user: true,
+            },
+        );
+        self
+    }
+}
+
+pub fn render(template: &str, vars: &Vars) -> String {
+    match parse(template) {
+        Ok(nodes) => {
+            let mut output = String::new();
+            render_nodes(&nodes, vars, &mut output);
+            output
+        }
+        Err(err) => {
+            error!("Failed to parse template '{template}': {err}");
+            template.to_string()
+        }
+    }
+}
+
+pub fn validate(template: &str, variables: &[&str]) -> Result<(), Error> {
+    fn check(nodes: &[Node], variables: &[&str]) -> Result<(), Error> {
+        for node in nodes {
+            match node {
+                Node::Text(_) => {}
+                Node::Variable { name, .. } if !variables.contains(&name.as_str()) => {
+                    return Err(Error::UnknownVariable(name.clone()));
+                }
+                Node::Variable { .. } => {}
+                Node::Section { name, children, .. } => {
+                    if !variables.contains(&name.as_str()) {
+                        return Err(Error::UnknownVariable(name.clone()));
+                    }
+
+                    check(children, variables)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    check(&parse(template)?, variables)
+}
+
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        if ESCAPED_CHARACTERS.contains(&character) {
+            escaped.push('\\');
+        }
+
+        escaped.push(character);
+    }
+
+    escaped
+}
+
+fn is_tag(tag: &str) -> bool {
+    let name = tag
+        .strip_prefix([SECTION, INVERTED_SECTION, SECTION_END])
+        .unwrap_or(tag);
+    let name = name.split(FILTER_SEPARATOR).next().unwrap_or_default();
+
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|character| character.is_ascii_lowercase() || character == '_')
+        && !tag.chars().any(char::is_whitespace)
+}
+
+fn parse(template: &str) -> Result<Vec<Node>, Error> {
+    let mut stack = vec![Frame {
+        section: None,
+        nodes: Vec::new(),
+    }];
+    let mut text = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find(TAG) {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        if let Some(after) = after.strip_prefix(TAG) {
+            text.push(TAG);
+            rest = after;
+            continue;
+        }
+
+        let Some(end) = after.find(TAG).filter(|end| is_tag(&after[..*end])) else {
+            text.push(TAG);
+            rest = after;
+            continue;
+        };
+
+        let tag = &after[..end];
+        rest = &after[end + 1..];
+
+        let frame = stack.last_mut().expect("The root is never popped.");
+        if !text.is_empty() {
+            frame.nodes.push(Node::Text(mem::take(&mut text)));
+        }
+
+        if let Some(name) = tag.strip_prefix(SECTION) {
+            stack.push(Frame {
+                section: Some((name.to_string(), false)),
+                nodes: Vec::new(),
+            });
+        } else if let Some(name) = tag.strip_prefix(INVERTED_SECTION) {
+            stack.push(Frame {
+                section: Some((name.to_string(), true)),
+                nodes: Vec::new(),
+            });
+        } else if let Some(name) = tag.strip_prefix(SECTION_END) {
+            let Some(Frame {
+                section: Some((open, inverted)),
+                nodes: children,
+            }) = stack.pop()
+            else {
+                return Err(Error::UnexpectedClose(name.to_string()));
+            };
+
+            if open != name {
+                return Err(Error::UnclosedSection(open));
+            }
+
+            let frame = stack.last_mut().expect("The root is never popped.");
+            frame.nodes.push(Node::Section {
+                name: open,
+                inverted,
+                children,
+            });
+        } else {
+            frame.nodes.push(parse_variable(tag)?);
+        }
+    }
+
+    text.push_str(rest);
+
+    let Frame { section, mut nodes } = stack.pop().expect("The root is never popped.");
+
+    if let Some((name, _)) = section {
+        return Err(Error::UnclosedSection(name));
+    }
+
+    if !text.is_empty() {
+        nodes.push(Node::Text(text));
+    }
+
+    Ok(nodes)
+}
+
+fn parse_variable(tag: &str) -> Result<Node, Error> {
+    let mut parts = tag.split(FILTER_SEPARATOR);
+    let name = parts.next().unwrap_or_default().to_string();
+
+    let filters = parts
+        .map(|filter| {
+            let (filter, argument) = match filter.split_once(ARGUMENT_SEPARATOR) {
+                Some((filter, argument)) => (filter, Some(argument)),
+                None => (filter, None),
+            };
+
+            let invalid = || {
+                Error::InvalidArgument(filter.to_string(), argument.unwrap_or_default().to_string())
+            };
+
+            match (filter, argument) {
+                ("raw", None) => Ok(Filter::Raw),
+                ("escape", None) => Ok(Filter::Escape),
+                ("upper", None) => Ok(Filter::Upper),
+                ("lower", None) => Ok(Filter::Lower),
+                ("code", None) => Ok(Filter::Code),
+                ("truncate", Some(length)) => match length.parse() {
+                    Ok(length) if length > 0 => Ok(Filter::Truncate(length)),
+                    _ => Err(invalid()),
+                },
+                ("time", None) => Ok(Filter::Time(DEFAULT_TIMESTAMP_STYLE.to_string())),
+                ("time", Some(style)) if TIMESTAMP_STYLES.contains(&style) => {
+                    Ok(Filter::Time(style.to_string()))
+                }
+                ("raw" | "escape" | "upper" | "lower" | "code" | "truncate" | "time", _) => {
+                    Err(invalid())
+                }
+                _ => Err(Error::UnknownFilter(filter.to_string())),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Node::Variable { name, filters })
+}
+
+fn render_nodes(nodes: &[Node], vars: &Vars, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Variable { name, filters } => {
+                if let Some(value) = vars.values.get(name.as_str()) {
+                    output.push_str(&apply(value, filters));
+                }
+            }
+            Node::Section {
+                name,
+                inverted,
+                children,
+            } => {
+                let is_set = vars
+                    .values
+                    .get(name.as_str())
+                    .is_some_and(|value| !value.text.is_empty());
+
+                if is_set != *inverted {
+                    render_nodes(children, vars, output);
+                }
+            }
+        }
+    }
+}
+
+fn apply(value: &Value, filters: &[Filter]) -> String {
+    let mut text = value.text.clone();
+    let mut escaped = value.user;
+
+    for filter in filters {
+        match filter {
+            Filter::Raw => escaped = false,
+            Filter::Escape => escaped = true,
+            Filter::Upper => text = text.to_uppercase(),
+            Filter::Lower => text = text.to_lowercase(),
+            Filter::Truncate(length) => {
+                if text.chars().count() > *length {
+                    (text, _) = truncate(&text, length - 1);
+                    text.push('…');
+                }
+            }
+            Filter::Code => {
+                text = format!("`{}`", text.replace('`', "ˋ"));
+                escaped = false;
+            }
+            Filter::Time(style) => {
+                text = format!("<t:{text}:{style}>");
+                escaped = false;
+            }
+        }
+    }
+
+    if escaped {
+        escape(&text)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_variables() {
+        let vars = Vars::new().set("name", "bob");
+
+        assert_eq!(render("Hi %name%!", &vars), "Hi bob!");
+        assert_eq!(render("Hi %missing%!", &vars), "Hi !");
+    }
+
+    #[test]
+    fn escapes_user_values() {
+        let vars = Vars::new().user("name", "*bob*");
+
+        assert_eq!(render("%name%", &vars), "\\*bob\\*");
+        assert_eq!(render("%name|raw%", &vars), "*bob*");
+    }
+
+    #[test]
+    fn keeps_literal_percent_signs() {
+        let vars = Vars::new().set("count", 5);
+
+        assert_eq!(render("100%% done", &vars), "100% done");
+        assert_eq!(render("50% of %count%", &vars), "50% of 5");
+        assert_eq!(render("100% sure 20% off", &vars), "100% sure 20% off");
+        assert_eq!(render("%", &vars), "%");
+    }
+
+    #[test]
+    fn renders_nested_sections() {
+        let template = "%?a%A%?b%B%/b%%!b%-%/b%%/a%";
+
+        assert_eq!(render(template, &Vars::new().set("a", 1).set("b", 1)), "AB");
+        assert_eq!(render(template, &Vars::new().set("a", 1)), "A-");
+        assert_eq!(render(template, &Vars::new().set("b", 1)), "");
+        assert_eq!(
+            render(template, &Vars::new().set("a", 1).set("b", "")),
+            "A-"
+        );
+    }
+
+    #[test]
+    fn rejects_unclosed_sections() {
+        assert!(matches!(
+            validate("%?a%text", &["a"]),
+            Err(Error::UnclosedSection(name)) if name == "a"
+        ));
+        assert!(matches!(
+            validate("%?a%%?b%%/a%%/b%", &["a", "b"]),
+            Err(Error::UnclosedSection(name)) if name == "b"
+        ));
+        assert!(matches!(
+            validate("text%/a%", &["a"]),
+            Err(Error::UnexpectedClose(name)) if name == "a"
+        ));
+    }
+
+    #[test]
+    fn falls_back_to_template_on_errors() {
+        assert_eq!(render("%?a%text", &Vars::new().set("a", 1)), "%?a%text");
+    }
+
+    #[test]
+    fn rejects_unknown_variables_and_filters() {
+        assert!(matches!(
+            validate("%name%", &["other"]),
+            Err(Error::UnknownVariable(name)) if name == "name"
+        ));
+        assert!(matches!(
+            validate("%?name%%/name%", &["other"]),
+            Err(Error::UnknownVariable(name)) if name == "name"
+        ));
+        assert!(matches!(
+            validate("%name|shout%", &["name"]),
+            Err(Error::UnknownFilter(filter)) if filter == "shout"
+        ));
+        assert!(matches!(
+            validate("%name|truncate:0%", &["name"]),
+            Err(Error::InvalidArgument(filter, argument)) if filter == "truncate" && argument == "0"
+        ));
+        assert!(matches!(
+            validate("%name|upper:1%", &["name"]),
+            Err(Error::InvalidArgument(filter, _)) if filter == "upper"
+        ));
+        assert!(matches!(
+            validate("%name|time:x%", &["name"]),
+            Err(Error::InvalidArgument(filter, argument)) if filter == "time" && argument == "x"
+        ));
+    }
+
+    #[test]
+    fn truncates_multi_byte_values() {
+        let vars = Vars::new().set("name", "héllo wörld");
+
+        assert_eq!(render("%name|truncate:5%", &vars), "héll…");
+        assert_eq!(render("%name|truncate:11%", &vars), "héllo wörld");
+
+        let vars = Vars::new().set("emoji", "👍🏽👍🏽👍🏽");
+
+        assert_eq!(render("%emoji|truncate:5%", &vars), "👍🏽👍🏽…");
+    }
+
+    #[test]
+    fn applies_filters_in_order() {
+        let vars = Vars::new().user("name", "Bob`s");
+
+        assert_eq!(render("%name|upper%", &vars), "BOB\\`S");
+        assert_eq!(render("%name|lower|code%", &vars), "`bobˋs`");
+        assert_eq!(
+            render("%time|time:R%", &Vars::new().set("time", 10)),
+            "<t:10:R>"
+        );
+    }
+}
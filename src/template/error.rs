@@ -0,0 +1,24 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownVariable(String),
+    UnknownFilter(String),
+    InvalidArgument(String, String),
+    UnclosedSection(String),
+    UnexpectedClose(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownVariable(name) => write!(f, "unknown variable '{name}'"),
+            Self::UnknownFilter(name) => write!(f, "unknown filter '{name}'"),
+            Self::InvalidArgument(filter, argument) => {
+                write!(f, "invalid argument '{argument}' for filter '{filter}'")
+            }
+            Self::UnclosedSection(name) => write!(f, "section '{name}' is never closed"),
+            Self::UnexpectedClose(name) => write!(f, "section '{name}' is closed but never opened"),
+        }
+    }
+}
@@ -1,23 +1,217 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serenity::{all::GuildId, client::Context};
+use tracing::{error, instrument};
+
+use crate::event::{
+    channel_create, channel_delete, channel_update, guild_ban_addition, guild_ban_removal,
+    guild_member_addition, guild_member_removal, guild_member_update, guild_role_create,
+    guild_role_delete, guild_role_update, message_delete, message_delete_bulk, message_update,
+    thread_create, thread_delete, thread_update, voice_state_update,
+};
+use crate::{
+    config::GuildConfig,
+    data::Data,
+    locale::Locale,
+    template::{self, Variables},
+};
+
+const VARIABLES: Variables = &[
+    ("retention_set", &["days"]),
+    ("ignore_channels", &["targets"]),
+    ("ignore_roles", &["targets"]),
+    ("ignore_users", &["targets"]),
+    ("text_invalid", &["error"]),
+    ("text_preview", &["text"]),
+    ("layout_invalid", &["error"]),
+    ("layout_show", &["layout", "fields"]),
+    ("setup_failed", &["error"]),
+    ("setup_leftover", &["channels"]),
+    ("manager_set", &["role"]),
+    ("missing_bot_permissions", &["permissions"]),
+    ("doctor_problems", &["problems"]),
+    ("doctor_missing_channel", &["channel", "events"]),
+    (
+        "doctor_missing_permissions",
+        &["channel", "events", "permissions"],
+    ),
+    ("doctor_disabled_channel", &["channel", "events"]),
+    ("doctor_unchecked_channel", &["channel", "events"]),
+    ("destination_failing", &["channel", "guild", "failures"]),
+    ("destination_disabled", &["channel", "guild", "failures"]),
+    ("alerts_set", &["target", "disable"]),
+    ("config_invalid", &["error"]),
+    ("config_too_large", &["size"]),
+    ("config_imported_skipped", &["count"]),
+    (
+        "config_settings",
+        &[
+            "retention",
+            "diff",
+            "attachments",
+            "webhooks",
+            "language",
+            "bots",
+            "formats",
+            "layouts",
+            "texts",
+            "manager",
+            "alerts",
+            "disable_failing",
+        ],
+    ),
+];
+
+const EVENT_VARIABLES: &[(&str, Variables)] = &[
+    ("message_delete", message_delete::VARIABLES),
+    ("message_update", message_update::VARIABLES),
+    ("message_delete_bulk", message_delete_bulk::VARIABLES),
+    ("guild_member_addition", guild_member_addition::VARIABLES),
+    ("guild_member_removal", guild_member_removal::VARIABLES),
+    ("guild_member_update", guild_member_update::VARIABLES),
+    ("guild_ban_addition", guild_ban_addition::VARIABLES),
+    ("guild_ban_removal", guild_ban_removal::VARIABLES),
+    ("voice_state_update", voice_state_update::VARIABLES),
+    ("channel_create", channel_create::VARIABLES),
+    ("channel_delete", channel_delete::VARIABLES),
+    ("channel_update", channel_update::VARIABLES),
+    ("thread_create", thread_create::VARIABLES),
+    ("thread_delete", thread_delete::VARIABLES),
+    ("thread_update", thread_update::VARIABLES),
+    ("guild_role_create", guild_role_create::VARIABLES),
+    ("guild_role_delete", guild_role_delete::VARIABLES),
+    ("guild_role_update", guild_role_update::VARIABLES),
+];
 
-use crate::event::{guild_member_addition, guild_member_removal, message_delete, message_update};
+pub fn variables(key: &str) -> &'static [&'static str] {
+    let (variables, key) = match key.split_once('.') {
+        Some((event, key)) => (
+            EVENT_VARIABLES
+                .iter()
+                .find(|(name, _)| *name == event)
+                .map_or(&[][..], |(_, variables)| *variables),
+            key,
+        ),
+        None => (VARIABLES, key),
+    };
+
+    variables
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map_or(&[], |(_, variables)| *variables)
+}
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Texts {
     error_embed_title: String,
-    missing_administrator_permission: String,
+    missing_manager_permission: String,
     error_command_executed_in_dm: String,
     internal_error: String,
     not_valid_channel: String,
     success_embed_title: String,
     channel_set: String,
     channel_unset: String,
+    retention_set: String,
+    webhooks_enabled: String,
+    webhooks_disabled: String,
+    webhook_identity_set: String,
+    webhook_identity_reset: String,
+    diff_mode_set: String,
+    attachment_capture_enabled: String,
+    attachment_capture_disabled: String,
+    ignore_target_missing: String,
+    ignore_added: String,
+    ignore_removed: String,
+    ignore_list_empty: String,
+    ignore_list_title: String,
+    ignore_all_events: String,
+    ignore_channels: String,
+    ignore_roles: String,
+    ignore_users: String,
+    bots_included: String,
+    bots_excluded: String,
+    text_unknown_key: String,
+    text_set: String,
+    text_reset: String,
+    text_preview: String,
+    language_set: String,
+    language_reset: String,
+    text_invalid: String,
+    layout_set: String,
+    layout_reset: String,
+    layout_invalid: String,
+    layout_show: String,
+    format_set: String,
+    ignore_destination_unknown: String,
+    setup_title: String,
+    setup_intro: String,
+    setup_create: String,
+    setup_map: String,
+    setup_cancel: String,
+    setup_apply: String,
+    setup_map_prompt: String,
+    setup_cancelled: String,
+    setup_timeout: String,
+    setup_failed: String,
+    setup_category: String,
+    setup_messages: String,
+    setup_members: String,
+    setup_voice: String,
+    setup_server: String,
+    setup_summary: String,
+    setup_not_logged: String,
+    config_show: String,
+    config_exported: String,
+    config_invalid: String,
+    config_imported: String,
+    config_imported_skipped: String,
+    config_destinations: String,
+    config_settings: String,
+    config_enabled: String,
+    config_disabled: String,
+    config_none: String,
+    config_default_language: String,
+    missing_manage_guild_permission: String,
+    manager_set: String,
+    manager_reset: String,
+    missing_bot_permissions: String,
+    doctor_ok: String,
+    doctor_problems: String,
+    doctor_missing_channel: String,
+    doctor_missing_permissions: String,
+    destination_unusable: String,
+    destination_failing: String,
+    destination_disabled: String,
+    doctor_disabled_channel: String,
+    alerts_owner: String,
+    alerts_set: String,
+    config_too_large: String,
+    setup_leftover: String,
+    doctor_unavailable: String,
+    doctor_unchecked_channel: String,
+    channel_set_unchecked: String,
 
     pub message_delete: message_delete::Texts,
     pub message_update: message_update::Texts,
+    pub message_delete_bulk: message_delete_bulk::Texts,
     pub guild_member_addition: guild_member_addition::Texts,
     pub guild_member_removal: guild_member_removal::Texts,
+    pub guild_member_update: guild_member_update::Texts,
+    pub guild_ban_addition: guild_ban_addition::Texts,
+    pub guild_ban_removal: guild_ban_removal::Texts,
+    pub voice_state_update: voice_state_update::Texts,
+    pub channel_create: channel_create::Texts,
+    pub channel_delete: channel_delete::Texts,
+    pub channel_update: channel_update::Texts,
+    pub thread_create: thread_create::Texts,
+    pub thread_delete: thread_delete::Texts,
+    pub thread_update: thread_update::Texts,
+    pub guild_role_create: guild_role_create::Texts,
+    pub guild_role_delete: guild_role_delete::Texts,
+    pub guild_role_update: guild_role_update::Texts,
 }
 
 impl Texts {
@@ -25,8 +219,8 @@ impl Texts {
         &self.error_embed_title
     }
 
-    pub fn missing_administrator_permission(&self) -> &str {
-        &self.missing_administrator_permission
+    pub fn missing_manager_permission(&self) -> &str {
+        &self.missing_manager_permission
     }
 
     pub fn error_command_executed_in_dm(&self) -> &str {
@@ -52,14 +246,330 @@ impl Texts {
     pub fn channel_unset(&self) -> &str {
         &self.channel_unset
     }
+
+    pub fn retention_set(&self) -> &str {
+        &self.retention_set
+    }
+
+    pub fn webhooks_enabled(&self) -> &str {
+        &self.webhooks_enabled
+    }
+
+    pub fn webhooks_disabled(&self) -> &str {
+        &self.webhooks_disabled
+    }
+
+    pub fn webhook_identity_set(&self) -> &str {
+        &self.webhook_identity_set
+    }
+
+    pub fn webhook_identity_reset(&self) -> &str {
+        &self.webhook_identity_reset
+    }
+
+    pub fn diff_mode_set(&self) -> &str {
+        &self.diff_mode_set
+    }
+
+    pub fn attachment_capture_enabled(&self) -> &str {
+        &self.attachment_capture_enabled
+    }
+
+    pub fn attachment_capture_disabled(&self) -> &str {
+        &self.attachment_capture_disabled
+    }
+
+    pub fn ignore_target_missing(&self) -> &str {
+        &self.ignore_target_missing
+    }
+
+    pub fn ignore_added(&self) -> &str {
+        &self.ignore_added
+    }
+
+    pub fn ignore_removed(&self) -> &str {
+        &self.ignore_removed
+    }
+
+    pub fn ignore_list_empty(&self) -> &str {
+        &self.ignore_list_empty
+    }
+
+    pub fn ignore_list_title(&self) -> &str {
+        &self.ignore_list_title
+    }
+
+    pub fn ignore_all_events(&self) -> &str {
+        &self.ignore_all_events
+    }
+
+    pub fn ignore_channels(&self) -> &str {
+        &self.ignore_channels
+    }
+
+    pub fn ignore_roles(&self) -> &str {
+        &self.ignore_roles
+    }
+
+    pub fn ignore_users(&self) -> &str {
+        &self.ignore_users
+    }
+
+    pub fn bots_included(&self) -> &str {
+        &self.bots_included
+    }
+
+    pub fn bots_excluded(&self) -> &str {
+        &self.bots_excluded
+    }
+
+    pub fn text_unknown_key(&self) -> &str {
+        &self.text_unknown_key
+    }
+
+    pub fn text_set(&self) -> &str {
+        &self.text_set
+    }
+
+    pub fn text_reset(&self) -> &str {
+        &self.text_reset
+    }
+
+    pub fn text_preview(&self) -> &str {
+        &self.text_preview
+    }
+
+    pub fn language_set(&self) -> &str {
+        &self.language_set
+    }
+
+    pub fn language_reset(&self) -> &str {
+        &self.language_reset
+    }
+
+    pub fn text_invalid(&self) -> &str {
+        &self.text_invalid
+    }
+
+    pub fn layout_set(&self) -> &str {
+        &self.layout_set
+    }
+
+    pub fn layout_reset(&self) -> &str {
+        &self.layout_reset
+    }
+
+    pub fn layout_invalid(&self) -> &str {
+        &self.layout_invalid
+    }
+
+    pub fn layout_show(&self) -> &str {
+        &self.layout_show
+    }
+
+    pub fn format_set(&self) -> &str {
+        &self.format_set
+    }
+
+    pub fn ignore_destination_unknown(&self) -> &str {
+        &self.ignore_destination_unknown
+    }
+
+    pub fn setup_title(&self) -> &str {
+        &self.setup_title
+    }
+
+    pub fn setup_intro(&self) -> &str {
+        &self.setup_intro
+    }
+
+    pub fn setup_create(&self) -> &str {
+        &self.setup_create
+    }
+
+    pub fn setup_map(&self) -> &str {
+        &self.setup_map
+    }
+
+    pub fn setup_cancel(&self) -> &str {
+        &self.setup_cancel
+    }
+
+    pub fn setup_apply(&self) -> &str {
+        &self.setup_apply
+    }
+
+    pub fn setup_map_prompt(&self) -> &str {
+        &self.setup_map_prompt
+    }
+
+    pub fn setup_cancelled(&self) -> &str {
+        &self.setup_cancelled
+    }
+
+    pub fn setup_timeout(&self) -> &str {
+        &self.setup_timeout
+    }
+
+    pub fn setup_failed(&self) -> &str {
+        &self.setup_failed
+    }
+
+    pub fn setup_category(&self) -> &str {
+        &self.setup_category
+    }
+
+    pub fn setup_messages(&self) -> &str {
+        &self.setup_messages
+    }
+
+    pub fn setup_members(&self) -> &str {
+        &self.setup_members
+    }
+
+    pub fn setup_voice(&self) -> &str {
+        &self.setup_voice
+    }
+
+    pub fn setup_server(&self) -> &str {
+        &self.setup_server
+    }
+
+    pub fn setup_summary(&self) -> &str {
+        &self.setup_summary
+    }
+
+    pub fn setup_not_logged(&self) -> &str {
+        &self.setup_not_logged
+    }
+
+    pub fn config_show(&self) -> &str {
+        &self.config_show
+    }
+
+    pub fn config_exported(&self) -> &str {
+        &self.config_exported
+    }
+
+    pub fn config_invalid(&self) -> &str {
+        &self.config_invalid
+    }
+
+    pub fn config_imported(&self) -> &str {
+        &self.config_imported
+    }
+
+    pub fn config_imported_skipped(&self) -> &str {
+        &self.config_imported_skipped
+    }
+
+    pub fn config_destinations(&self) -> &str {
+        &self.config_destinations
+    }
+
+    pub fn config_settings(&self) -> &str {
+        &self.config_settings
+    }
+
+    pub fn config_enabled(&self) -> &str {
+        &self.config_enabled
+    }
+
+    pub fn config_disabled(&self) -> &str {
+        &self.config_disabled
+    }
+
+    pub fn config_none(&self) -> &str {
+        &self.config_none
+    }
+
+    pub fn config_default_language(&self) -> &str {
+        &self.config_default_language
+    }
+
+    pub fn missing_manage_guild_permission(&self) -> &str {
+        &self.missing_manage_guild_permission
+    }
+
+    pub fn manager_set(&self) -> &str {
+        &self.manager_set
+    }
+
+    pub fn manager_reset(&self) -> &str {
+        &self.manager_reset
+    }
+
+    pub fn missing_bot_permissions(&self) -> &str {
+        &self.missing_bot_permissions
+    }
+
+    pub fn doctor_ok(&self) -> &str {
+        &self.doctor_ok
+    }
+
+    pub fn doctor_problems(&self) -> &str {
+        &self.doctor_problems
+    }
+
+    pub fn doctor_missing_channel(&self) -> &str {
+        &self.doctor_missing_channel
+    }
+
+    pub fn doctor_missing_permissions(&self) -> &str {
+        &self.doctor_missing_permissions
+    }
+
+    pub fn destination_unusable(&self) -> &str {
+        &self.destination_unusable
+    }
+
+    pub fn destination_failing(&self) -> &str {
+        &self.destination_failing
+    }
+
+    pub fn destination_disabled(&self) -> &str {
+        &self.destination_disabled
+    }
+
+    pub fn doctor_disabled_channel(&self) -> &str {
+        &self.doctor_disabled_channel
+    }
+
+    pub fn alerts_owner(&self) -> &str {
+        &self.alerts_owner
+    }
+
+    pub fn alerts_set(&self) -> &str {
+        &self.alerts_set
+    }
+
+    pub fn config_too_large(&self) -> &str {
+        &self.config_too_large
+    }
+
+    pub fn setup_leftover(&self) -> &str {
+        &self.setup_leftover
+    }
+
+    pub fn doctor_unavailable(&self) -> &str {
+        &self.doctor_unavailable
+    }
+
+    pub fn doctor_unchecked_channel(&self) -> &str {
+        &self.doctor_unchecked_channel
+    }
+
+    pub fn channel_set_unchecked(&self) -> &str {
+        &self.channel_set_unchecked
+    }
 }
 
 impl Default for Texts {
     fn default() -> Self {
         Self {
             error_embed_title: String::from("Error"),
-            missing_administrator_permission: String::from(
-                ":x: You must be an administrator to execute this command.",
+            missing_manager_permission: String::from(
+                ":x: You need the Manage Server permission or the log manager role to execute this command.",
             ),
             error_command_executed_in_dm: String::from(
                 ":x: This command can only be executed in a guild.",
@@ -71,11 +581,273 @@ impl Default for Texts {
             success_embed_title: String::from("Success"),
             channel_set: String::from("Channel was set."),
             channel_unset: String::from("Channel was unset."),
+            retention_set: String::from(
+                "Messages will be kept for %days% days. Use 0 to stop storing messages.",
+            ),
+            webhooks_enabled: String::from("Logs will be delivered through webhooks."),
+            webhooks_disabled: String::from("Logs will be delivered by the bot."),
+            webhook_identity_set: String::from("Webhook identity was set."),
+            webhook_identity_reset: String::from("Webhook identity was reset."),
+            diff_mode_set: String::from(
+                "Edited messages will be shown with the selected diff mode.",
+            ),
+            attachment_capture_enabled: String::from(
+                "Attachments will be saved locally and re-uploaded when their message is deleted.",
+            ),
+            attachment_capture_disabled: String::from(
+                "Attachments will no longer be saved locally.",
+            ),
+            ignore_target_missing: String::from("Choose at least one channel, role or user."),
+            ignore_added: String::from("The selection will be ignored by the logs."),
+            ignore_removed: String::from("The selection will no longer be ignored by the logs."),
+            ignore_list_empty: String::from("Nothing is being ignored."),
+            ignore_list_title: String::from("Ignored"),
+            ignore_all_events: String::from("All Events"),
+            ignore_channels: String::from("Channels: %targets%"),
+            ignore_roles: String::from("Roles: %targets%"),
+            ignore_users: String::from("Users: %targets%"),
+            bots_included: String::from("Bots and webhooks will be included in this event's logs."),
+            bots_excluded: String::from(
+                "Bots and webhooks will be excluded from this event's logs.",
+            ),
+            text_unknown_key: String::from("That text key doesn't exist."),
+            text_set: String::from("The text was changed for this server. It is used whatever the language of the logs is."),
+            text_reset: String::from("The text was reset to its default."),
+            text_preview: String::from("```\n%text%\n```"),
+            language_set: String::from("Logs will be written in the selected language."),
+            language_reset: String::from("Logs will be written in the server's language."),
+            text_invalid: String::from("The text is not valid: %error%."),
+            layout_set: String::from("The layout has been set."),
+            layout_reset: String::from("The layout has been reset."),
+            layout_invalid: String::from("The layout is not valid: %error%."),
+            layout_show: String::from(
+                "Layout:\n```json\n%layout%\n```\nAvailable fields: %fields%",
+            ),
+            format_set: String::from("The log format has been changed."),
+            ignore_destination_unknown: String::from(
+                "That channel is not a log destination of this event.",
+            ),
+            setup_title: String::from("Logging setup"),
+            setup_intro: String::from("Create a new log category with a channel for each group of events, or map existing channels."),
+            setup_create: String::from("Create channels"),
+            setup_map: String::from("Use existing channels"),
+            setup_cancel: String::from("Cancel"),
+            setup_apply: String::from("Apply"),
+            setup_map_prompt: String::from("Choose a channel for each group of events. Groups without a channel are left unchanged."),
+            setup_cancelled: String::from("The setup was cancelled."),
+            setup_timeout: String::from("The setup timed out."),
+            setup_failed: String::from("The log channels could not be created: %error%."),
+            setup_category: String::from("Logs"),
+            setup_messages: String::from("Messages"),
+            setup_members: String::from("Members"),
+            setup_voice: String::from("Voice"),
+            setup_server: String::from("Server"),
+            setup_summary: String::from("Setup complete. Events are logged as follows:"),
+            setup_not_logged: String::from("Not logged"),
+            config_show: String::from("Server configuration"),
+            config_exported: String::from("The configuration of this server is attached."),
+            config_invalid: String::from("The configuration is not valid: %error%."),
+            config_imported: String::from("The configuration was imported."),
+            config_imported_skipped: String::from("The configuration was imported. %count% log channels were skipped because they don't exist in this server."),
+            config_destinations: String::from("**Log channels**"),
+            config_settings: String::from(
+                "**Settings**\nMessage retention: %retention% days\nDiff mode: %diff%\nAttachment capture: %attachments%\nWebhooks: %webhooks%\nLanguage: %language%\nBots included in: %bots%\nOutput formats: %formats%\nCustom layouts: %layouts%\nCustom texts: %texts%\nLog manager role: %manager%\nAlerts sent to: %alerts%\nDisable failing channels: %disable_failing%",
+            ),
+            config_enabled: String::from("Enabled"),
+            config_disabled: String::from("Disabled"),
+            config_none: String::from("None"),
+            config_default_language: String::from("Server default"),
+            missing_manage_guild_permission: String::from(":x: You need the Manage Server permission to execute this command."),
+            manager_set: String::from("Members with %role% can now change the logging settings."),
+            manager_reset: String::from("Only members with the Manage Server permission can change the logging settings now."),
+            missing_bot_permissions: String::from("I'm missing these permissions in that channel: %permissions%."),
+            doctor_ok: String::from("All log channels are set up correctly."),
+            doctor_problems: String::from("Some log channels have problems:\n%problems%"),
+            doctor_missing_channel: String::from("%channel% no longer exists (%events%)"),
+            doctor_missing_permissions: String::from("%channel% is missing %permissions% (%events%)"),
+            destination_unusable: String::from("Log channel unusable"),
+            destination_failing: String::from("Logs for %channel% in **%guild%** failed to be delivered %failures% times in a row. Run `/logs doctor` in the server to find out why."),
+            destination_disabled: String::from("Logs for %channel% in **%guild%** failed to be delivered %failures% times in a row, so the channel was disabled. Fix the problem and use `/set` on the channel again to enable it."),
+            doctor_disabled_channel: String::from("%channel% was disabled after repeated delivery failures (%events%)"),
+            alerts_owner: String::from("the server owner"),
+            alerts_set: String::from("Alerts about unusable log channels will be sent to %target%. Disabling failing log channels: %disable%."),
+            config_too_large: String::from("The configuration file is larger than %size% bytes."),
+            setup_leftover: String::from("These channels were created before the error and could not be removed: %channels%."),
+            doctor_unavailable: String::from("The server could not be checked right now. Try again later."),
+            doctor_unchecked_channel: String::from("%channel% could not be checked (%events%)"),
+            channel_set_unchecked: String::from("Channel was set, but its permissions could not be checked."),
 
             message_delete: message_delete::Texts::default(),
             message_update: message_update::Texts::default(),
+            message_delete_bulk: message_delete_bulk::Texts::default(),
             guild_member_addition: guild_member_addition::Texts::default(),
             guild_member_removal: guild_member_removal::Texts::default(),
+            guild_member_update: guild_member_update::Texts::default(),
+            guild_ban_addition: guild_ban_addition::Texts::default(),
+            guild_ban_removal: guild_ban_removal::Texts::default(),
+            voice_state_update: voice_state_update::Texts::default(),
+            channel_create: channel_create::Texts::default(),
+            channel_delete: channel_delete::Texts::default(),
+            channel_update: channel_update::Texts::default(),
+            thread_create: thread_create::Texts::default(),
+            thread_delete: thread_delete::Texts::default(),
+            thread_update: thread_update::Texts::default(),
+            guild_role_create: guild_role_create::Texts::default(),
+            guild_role_delete: guild_role_delete::Texts::default(),
+            guild_role_update: guild_role_update::Texts::default(),
+        }
+    }
+}
+
+impl Texts {
+    pub fn keys(&self) -> Vec<String> {
+        fn flatten(prefix: &str, value: &Value, keys: &mut Vec<String>) {
+            let Value::Object(map) = value else {
+                keys.push(prefix.to_string());
+                return;
+            };
+
+            for (key, value) in map {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+
+                flatten(&key, value, keys);
+            }
+        }
+
+        let mut keys = Vec::new();
+
+        if let Ok(value) = serde_json::to_value(self) {
+            flatten("", &value, &mut keys);
+        }
+
+        keys
+    }
+
+    pub fn text(&self, key: &str) -> Option<String> {
+        let value = serde_json::to_value(self).ok()?;
+
+        key.split('.')
+            .try_fold(&value, |value, key| value.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<(String, template::error::Error)>> {
+        let errors = self
+            .keys()
+            .into_iter()
+            .filter_map(|key| {
+                let text = self.text(&key)?;
+                let result = template::validate(&text, variables(&key));
+                result.err().map(|err| (key, err))
+            })
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    #[instrument(skip(self, overrides))]
+    pub fn with_overrides(&self, overrides: &BTreeMap<String, String>) -> Cow<'_, Texts> {
+        if overrides.is_empty() {
+            return Cow::Borrowed(self);
+        }
+
+        let mut value = match serde_json::to_value(self) {
+            Ok(value) => value,
+            Err(err) => {
+                error!("Failed to serialize texts to JSON: {err}");
+                return Cow::Borrowed(self);
+            }
+        };
+
+        for (key, text) in overrides {
+            let target = key
+                .split('.')
+                .try_fold(&mut value, |value, key| value.get_mut(key));
+
+            if let Some(target @ Value::String(_)) = target {
+                *target = Value::String(text.clone());
+            }
         }
+
+        match serde_json::from_value(value) {
+            Ok(texts) => Cow::Owned(texts),
+            Err(err) => {
+                error!("Failed to deserialize texts from JSON: {err}");
+                Cow::Borrowed(self)
+            }
+        }
+    }
+}
+
+#[instrument(skip(ctx, texts))]
+pub async fn for_guild<'a>(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    texts: &'a Texts,
+) -> Cow<'a, Texts> {
+    resolve(ctx, guild_id, None, texts).await
+}
+
+#[instrument(skip(ctx, texts))]
+pub async fn for_interaction<'a>(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    locale: Option<&str>,
+    texts: &'a Texts,
+) -> Cow<'a, Texts> {
+    resolve(ctx, guild_id, locale.and_then(Locale::from_discord), texts).await
+}
+
+async fn resolve<'a>(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    locale: Option<Locale>,
+    texts: &'a Texts,
+) -> Cow<'a, Texts> {
+    let data = ctx.data.read().await;
+    let data = data.get::<Data>().expect("Data should never be none.");
+
+    let config = match guild_id {
+        Some(guild_id) => match data.cache.get_or_insert(guild_id.get()).await {
+            Ok(config) => Some(config),
+            Err(err) => {
+                error!("Failed to get GuildConfig: {err:?}");
+                None
+            }
+        },
+        None => None,
+    };
+
+    let locale = locale
+        .or_else(|| config.as_ref().and_then(GuildConfig::locale))
+        .or_else(|| {
+            guild_id
+                .and_then(|guild_id| guild_id.to_guild_cached(ctx))
+                .and_then(|guild| Locale::from_discord(&guild.preferred_locale))
+        })
+        .unwrap_or_default();
+
+    let no_overrides = BTreeMap::new();
+    let overrides = config
+        .as_ref()
+        .map_or(&no_overrides, GuildConfig::text_overrides);
+
+    // The host's texts.json only customises English, other languages use their bundled texts.
+    // Server overrides from `/text set` apply on top of whichever language was picked.
+    match locale {
+        Locale::English => texts.with_overrides(overrides),
+        locale => match data.translations.get(&locale) {
+            Some(translation) => Cow::Owned(translation.with_overrides(overrides).into_owned()),
+            None => texts.with_overrides(overrides),
+        },
     }
 }
@@ -1 +1,5 @@
+pub mod diff;
+pub mod embed;
+pub mod format;
+pub mod permissions;
 pub mod text;
@@ -0,0 +1,395 @@
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::text::into_blocks;
+
+const MAX_DIFF_CELLS: usize = 250_000;
+const CONTEXT_TOKENS: usize = 6;
+const ELLIPSIS: &str = "…";
+const BLOCK_START: &str = "```diff\n";
+const BLOCK_END: &str = "\n```";
+
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize, poise::ChoiceParameter,
+)]
+pub enum DiffMode {
+    #[default]
+    #[name = "Full Content"]
+    #[serde(rename = "f")]
+    Full,
+    #[name = "Inline Markers"]
+    #[serde(rename = "i")]
+    Inline,
+    #[name = "Diff Code Block"]
+    #[serde(rename = "b")]
+    Block,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Equal,
+    Delete,
+    Insert,
+}
+
+fn diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Kind, &'a str)> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let mut changes = old[..prefix]
+        .iter()
+        .map(|token| (Kind::Equal, *token))
+        .collect::<Vec<_>>();
+
+    changes.extend(lcs(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    changes.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|token| (Kind::Equal, *token)),
+    );
+
+    changes
+}
+
+fn lcs<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Kind, &'a str)> {
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        return old
+            .iter()
+            .map(|token| (Kind::Delete, *token))
+            .chain(new.iter().map(|token| (Kind::Insert, *token)))
+            .collect();
+    }
+
+    let width = new.len() + 1;
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            changes.push((Kind::Equal, old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            changes.push((Kind::Delete, old[i]));
+            i += 1;
+        } else {
+            changes.push((Kind::Insert, new[j]));
+            j += 1;
+        }
+    }
+
+    changes.extend(old[i..].iter().map(|token| (Kind::Delete, *token)));
+    changes.extend(new[j..].iter().map(|token| (Kind::Insert, *token)));
+
+    changes
+}
+
+fn group(changes: Vec<(Kind, &str)>) -> Vec<(Kind, String)> {
+    let mut groups: Vec<(Kind, String)> = Vec::new();
+
+    for (kind, token) in changes {
+        match groups.last_mut() {
+            Some((last, text)) if *last == kind => text.push_str(token),
+            _ => groups.push((kind, token.to_string())),
+        }
+    }
+
+    groups
+}
+
+fn pack(pieces: Vec<String>, max_length: usize, separator: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+
+    for piece in pieces {
+        let length = current.chars().count() + separator.len() + piece.chars().count();
+
+        if !current.is_empty() && length > max_length {
+            blocks.push(std::mem::take(&mut current));
+        }
+
+        if !current.is_empty() {
+            current.push_str(separator);
+        }
+
+        current.push_str(&piece);
+    }
+
+    if !current.is_empty() || blocks.is_empty() {
+        blocks.push(current);
+    }
+
+    blocks
+}
+
+fn mark(text: &str, marker: &str, max_length: usize, pieces: &mut Vec<String>) {
+    let trimmed = text.trim();
+
+    if trimmed.is_empty() {
+        pieces.push(text.to_string());
+        return;
+    }
+
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+
+    if !leading.is_empty() {
+        pieces.push(leading.to_string());
+    }
+
+    for block in into_blocks(trimmed, max_length - marker.len() * 2) {
+        pieces.push(format!("{marker}{block}{marker}"));
+    }
+
+    if !trailing.is_empty() {
+        pieces.push(trailing.to_string());
+    }
+}
+
+pub fn inline(old: &str, new: &str, max_length: usize) -> Vec<String> {
+    let old = old.split_word_bounds().collect::<Vec<_>>();
+    let new = new.split_word_bounds().collect::<Vec<_>>();
+
+    let mut pieces = Vec::new();
+
+    for (kind, text) in group(diff(&old, &new)) {
+        match kind {
+            Kind::Equal => pieces.extend(into_blocks(&text, max_length)),
+            Kind::Delete => mark(&text, "~~", max_length, &mut pieces),
+            Kind::Insert => mark(&text, "**", max_length, &mut pieces),
+        }
+    }
+
+    pack(pieces, max_length, "")
+}
+
+// Keeps only the changed words of a line and a few words around them
+fn condense(changes: &[(Kind, &str)], hidden: Kind) -> String {
+    let tokens = changes
+        .iter()
+        .filter(|(kind, _)| *kind != hidden)
+        .collect::<Vec<_>>();
+
+    let mut keep = vec![false; tokens.len()];
+
+    for (index, (kind, _)) in tokens.iter().enumerate() {
+        if *kind != Kind::Equal {
+            let end = (index + CONTEXT_TOKENS + 1).min(tokens.len());
+            keep[index.saturating_sub(CONTEXT_TOKENS)..end].fill(true);
+        }
+    }
+
+    let mut text = String::new();
+    let mut elided = false;
+
+    for ((_, token), keep) in tokens.iter().zip(keep) {
+        if keep {
+            text.push_str(token);
+            elided = false;
+        } else if !elided {
+            text.push_str(ELLIPSIS);
+            elided = true;
+        }
+    }
+
+    text
+}
+
+fn changed_lines(deleted: &[&str], inserted: &[&str]) -> (Vec<String>, Vec<String>) {
+    let paired = deleted.len().min(inserted.len());
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+
+    for (old, new) in deleted.iter().zip(inserted) {
+        let old = old.split_word_bounds().collect::<Vec<_>>();
+        let new = new.split_word_bounds().collect::<Vec<_>>();
+        let changes = diff(&old, &new);
+
+        removed.push(condense(&changes, Kind::Insert));
+        added.push(condense(&changes, Kind::Delete));
+    }
+
+    removed.extend(deleted[paired..].iter().map(ToString::to_string));
+    added.extend(inserted[paired..].iter().map(ToString::to_string));
+
+    (removed, added)
+}
+
+pub fn block(old: &str, new: &str, max_length: usize) -> Vec<String> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    let max_line_length = max_length - BLOCK_START.len() - BLOCK_END.len();
+
+    let mut lines = Vec::new();
+    let mut push = |prefix: &str, line: &str| {
+        lines.extend(
+            into_blocks(&line.replace("```", "`\u{200b}``"), max_line_length - 2)
+                .into_iter()
+                .map(|line| format!("{prefix}{line}")),
+        );
+    };
+
+    let changes = diff(&old, &new);
+    let mut index = 0;
+
+    while index < changes.len() {
+        if let (Kind::Equal, line) = changes[index] {
+            push("  ", line);
+            index += 1;
+            continue;
+        }
+
+        let deleted = changes[index..]
+            .iter()
+            .take_while(|(kind, _)| *kind == Kind::Delete)
+            .map(|(_, line)| *line)
+            .collect::<Vec<_>>();
+        let inserted = changes[index + deleted.len()..]
+            .iter()
+            .take_while(|(kind, _)| *kind == Kind::Insert)
+            .map(|(_, line)| *line)
+            .collect::<Vec<_>>();
+
+        index += deleted.len() + inserted.len();
+
+        let (removed, added) = changed_lines(&deleted, &inserted);
+
+        for line in removed {
+            push("- ", &line);
+        }
+
+        for line in added {
+            push("+ ", &line);
+        }
+    }
+
+    pack(lines, max_line_length, "\n")
+        .into_iter()
+        .map(|block| format!("{BLOCK_START}{block}{BLOCK_END}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_marks_insertions() {
+        assert_eq!(
+            inline("hello world", "hello brave world", 1024),
+            vec!["hello **brave** world"]
+        );
+    }
+
+    #[test]
+    fn inline_marks_deletions() {
+        assert_eq!(inline("a b c", "a c", 1024), vec!["a ~~b~~ c"]);
+    }
+
+    #[test]
+    fn inline_splits_into_blocks() {
+        let old = "word ".repeat(100);
+        let new = format!("{old}extra");
+
+        for block in inline(&old, &new, 64) {
+            assert!(block.chars().count() <= 64);
+        }
+    }
+
+    #[test]
+    fn block_prefixes_lines() {
+        assert_eq!(
+            block("same\nold line", "same\nnew line\nadded", 1024),
+            vec!["```diff\n  same\n- old line\n+ new line\n+ added\n```"]
+        );
+    }
+
+    #[test]
+    fn block_condenses_changed_words() {
+        let words = (0..40).map(|i| format!("word{i}")).collect::<Vec<_>>();
+        let old = words.join(" ");
+        let new = old.replace("word20", "changed");
+
+        let blocks = block(&old, &new, 1024);
+        let output = blocks.join("");
+
+        assert_eq!(blocks.len(), 1);
+        assert!(output.contains("- …"));
+        assert!(output.contains("word20"));
+        assert!(output.contains("changed"));
+        assert!(!output.contains("word0 "));
+        assert!(output.len() < old.len());
+    }
+
+    #[test]
+    fn diff_falls_back_above_cap() {
+        let old = (0..600).map(|i| format!("a{i}")).collect::<Vec<_>>();
+        let new = (0..600).map(|i| format!("b{i}")).collect::<Vec<_>>();
+        let old = old.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut new = new.iter().map(String::as_str).collect::<Vec<_>>();
+        new[300] = old[300];
+
+        let changes = diff(&old, &new);
+
+        assert_eq!(changes.len(), 1200);
+        assert!(changes[..600].iter().all(|(kind, _)| *kind == Kind::Delete));
+        assert!(changes[600..].iter().all(|(kind, _)| *kind == Kind::Insert));
+    }
+
+    #[test]
+    fn diff_keeps_common_prefix_and_suffix_above_cap() {
+        let tokens = (0..1000).map(|i| format!("t{i}")).collect::<Vec<_>>();
+        let old = tokens.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut new = old.clone();
+        new[500] = "changed";
+
+        let changes = diff(&old, &new);
+
+        assert_eq!(changes.len(), 1001);
+        assert_eq!(
+            changes
+                .iter()
+                .filter(|(kind, _)| *kind != Kind::Equal)
+                .collect::<Vec<_>>(),
+            vec![&(Kind::Delete, "t500"), &(Kind::Insert, "changed")]
+        );
+    }
+
+    #[test]
+    fn inline_handles_multi_byte_graphemes() {
+        assert_eq!(
+            inline("héllo wörld 👍🏽", "héllo wörld 👍🏿", 1024),
+            vec!["héllo wörld ~~👍🏽~~**👍🏿**"]
+        );
+
+        let old = "👨‍👩‍👧".repeat(20);
+        for block in inline(&old, "", 10) {
+            assert!(block.chars().count() <= 10);
+            assert!(block.replace("~~", "").chars().count() % 5 == 0);
+        }
+    }
+}
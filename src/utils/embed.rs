@@ -0,0 +1,293 @@
+use serenity::all::{CreateAttachment, Embed, EmbedField};
+
+use super::text::{into_blocks, truncate};
+
+pub const MAX_EMBEDS_PER_MESSAGE: usize = 10;
+pub const MAX_CHARACTERS_PER_MESSAGE: usize = 6000;
+
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+const MAX_FIELDS: usize = 25;
+const MAX_FIELD_NAME_LENGTH: usize = 256;
+const MAX_FIELD_VALUE_LENGTH: usize = 1024;
+const MAX_FOOTER_LENGTH: usize = 2048;
+const MAX_AUTHOR_LENGTH: usize = 256;
+const EMPTY_FIELD: &str = "\u{200b}";
+pub const OVERFLOW_FILE_NAME: &str = "log.txt";
+
+pub fn length(embed: &Embed) -> usize {
+    let count = |text: &Option<String>| text.as_ref().map_or(0, |text| text.chars().count());
+
+    count(&embed.title)
+        + count(&embed.description)
+        + embed
+            .footer
+            .as_ref()
+            .map_or(0, |footer| footer.text.chars().count())
+        + embed
+            .author
+            .as_ref()
+            .map_or(0, |author| author.name.chars().count())
+        + embed
+            .fields
+            .iter()
+            .map(|field| field.name.chars().count() + field.value.chars().count())
+            .sum::<usize>()
+}
+
+pub fn fit(embeds: Vec<Embed>) -> (Vec<Embed>, Option<CreateAttachment>) {
+    let split = embeds.iter().flat_map(split).collect::<Vec<_>>();
+
+    if split.len() <= MAX_EMBEDS_PER_MESSAGE
+        && split.iter().map(length).sum::<usize>() <= MAX_CHARACTERS_PER_MESSAGE
+    {
+        return (split, None);
+    }
+
+    let mut kept = Vec::new();
+    let mut remaining = MAX_CHARACTERS_PER_MESSAGE;
+
+    for embed in split {
+        let length = length(&embed);
+        if kept.len() == MAX_EMBEDS_PER_MESSAGE || length > remaining {
+            break;
+        }
+
+        remaining -= length;
+        kept.push(embed);
+    }
+
+    let transcript = embeds.iter().map(render).collect::<Vec<_>>().join("\n\n");
+
+    (
+        kept,
+        Some(CreateAttachment::bytes(
+            transcript.into_bytes(),
+            OVERFLOW_FILE_NAME,
+        )),
+    )
+}
+
+pub fn shorten(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_string();
+    }
+
+    let (mut result, _) = truncate(text, max_length - 1);
+    result.push('…');
+
+    result
+}
+
+// Discord rejects fields with an empty name or value
+fn non_empty(text: String) -> String {
+    if text.trim().is_empty() {
+        EMPTY_FIELD.to_string()
+    } else {
+        text
+    }
+}
+
+fn split(embed: &Embed) -> Vec<Embed> {
+    let follow_up = || {
+        let mut follow_up = Embed::default();
+        follow_up.colour = embed.colour;
+        follow_up
+    };
+
+    let mut first = embed.clone();
+    first.fields = Vec::new();
+    first.title = first.title.map(|title| shorten(&title, MAX_TITLE_LENGTH));
+
+    if let Some(author) = &mut first.author {
+        author.name = shorten(&author.name, MAX_AUTHOR_LENGTH);
+    }
+
+    if let Some(footer) = &mut first.footer {
+        footer.text = shorten(&footer.text, MAX_FOOTER_LENGTH);
+    }
+
+    let description = first.description.take().unwrap_or_default();
+
+    // The title, author and footer share the first embed's character budget with the description
+    let first_length = MAX_DESCRIPTION_LENGTH.min(MAX_CHARACTERS_PER_MESSAGE - length(&first));
+    let (head, rest) = truncate(&description, first_length);
+
+    first.description = (!head.is_empty()).then_some(head);
+
+    let mut embeds = vec![first];
+
+    if !rest.is_empty() {
+        for block in into_blocks(&rest, MAX_DESCRIPTION_LENGTH) {
+            let mut embed = follow_up();
+            embed.description = Some(block);
+            embeds.push(embed);
+        }
+    }
+
+    let fields = embed.fields.iter().flat_map(|field| {
+        let name = non_empty(shorten(&field.name, MAX_FIELD_NAME_LENGTH));
+
+        into_blocks(&field.value, MAX_FIELD_VALUE_LENGTH)
+            .into_iter()
+            .map(move |value| EmbedField::new(name.clone(), non_empty(value), field.inline))
+    });
+
+    for field in fields {
+        let current = embeds.last().expect("There is always a first embed.");
+        let field_length = field.name.chars().count() + field.value.chars().count();
+
+        if current.fields.len() == MAX_FIELDS
+            || length(current) + field_length > MAX_CHARACTERS_PER_MESSAGE
+        {
+            embeds.push(follow_up());
+        }
+
+        embeds
+            .last_mut()
+            .expect("There is always a first embed.")
+            .fields
+            .push(field);
+    }
+
+    embeds
+}
+
+pub fn render(embed: &Embed) -> String {
+    let mut sections = Vec::new();
+
+    if let Some(author) = &embed.author {
+        sections.push(author.name.clone());
+    }
+
+    if let Some(title) = &embed.title {
+        sections.push(title.clone());
+    }
+
+    if let Some(description) = &embed.description {
+        sections.push(description.clone());
+    }
+
+    for field in &embed.fields {
+        sections.push(format!("{}\n{}", field.name, field.value));
+    }
+
+    if let Some(footer) = &embed.footer {
+        sections.push(footer.text.clone());
+    }
+
+    sections.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn embed(value: serde_json::Value) -> Embed {
+        serde_json::from_value(value).expect("Test embeds are valid.")
+    }
+
+    #[test]
+    fn shorten_keeps_text_at_limit() {
+        assert_eq!(shorten("abcde", 5), "abcde");
+        assert_eq!(shorten("ééééé", 5), "ééééé");
+    }
+
+    #[test]
+    fn shorten_truncates_above_limit() {
+        assert_eq!(shorten("abcdef", 5), "abcd…");
+        assert_eq!(shorten("éééééé", 5).chars().count(), 5);
+    }
+
+    #[test]
+    fn split_keeps_first_embed_under_total_limit() {
+        let embeds = split(&embed(json!({
+            "title": "t".repeat(300),
+            "author": { "name": "a".repeat(300) },
+            "footer": { "text": "f".repeat(3000) },
+            "description": "d".repeat(10000),
+        })));
+
+        assert!(embeds
+            .iter()
+            .all(|embed| length(embed) <= MAX_CHARACTERS_PER_MESSAGE));
+        assert!(embeds.iter().all(|embed| embed
+            .description
+            .as_ref()
+            .is_none_or(|description| description.chars().count() <= MAX_DESCRIPTION_LENGTH)));
+        assert_eq!(
+            embeds
+                .iter()
+                .filter_map(|embed| embed.description.as_ref())
+                .map(|description| description.chars().count())
+                .sum::<usize>(),
+            10000
+        );
+    }
+
+    #[test]
+    fn split_replaces_empty_fields() {
+        let embeds = split(&embed(json!({
+            "fields": [{ "name": "", "value": "", "inline": false }],
+        })));
+
+        assert_eq!(embeds.len(), 1);
+        assert_eq!(embeds[0].fields[0].name, EMPTY_FIELD);
+        assert_eq!(embeds[0].fields[0].value, EMPTY_FIELD);
+    }
+
+    #[test]
+    fn split_spreads_long_fields() {
+        let embeds = split(&embed(json!({
+            "fields": [{ "name": "n", "value": "v".repeat(MAX_FIELD_VALUE_LENGTH + 1), "inline": false }],
+        })));
+
+        assert_eq!(embeds.len(), 1);
+        assert_eq!(embeds[0].fields.len(), 2);
+        assert_eq!(embeds[0].fields[1].value, "v");
+    }
+
+    #[test]
+    fn split_limits_fields_per_embed() {
+        let fields = (0..MAX_FIELDS + 1)
+            .map(|i| json!({ "name": i.to_string(), "value": "v", "inline": true }))
+            .collect::<Vec<_>>();
+        let embeds = split(&embed(json!({ "fields": fields })));
+
+        assert_eq!(embeds.len(), 2);
+        assert_eq!(embeds[0].fields.len(), MAX_FIELDS);
+        assert_eq!(embeds[1].fields.len(), 1);
+    }
+
+    #[test]
+    fn fit_keeps_embeds_within_limits() {
+        let embeds = vec![embed(json!({ "description": "d" })); MAX_EMBEDS_PER_MESSAGE];
+        let (kept, overflow) = fit(embeds);
+
+        assert_eq!(kept.len(), MAX_EMBEDS_PER_MESSAGE);
+        assert!(overflow.is_none());
+    }
+
+    #[test]
+    fn fit_moves_extra_embeds_to_file() {
+        let embeds = vec![embed(json!({ "description": "d" })); MAX_EMBEDS_PER_MESSAGE + 1];
+        let (kept, overflow) = fit(embeds);
+
+        assert_eq!(kept.len(), MAX_EMBEDS_PER_MESSAGE);
+        assert!(overflow.is_some());
+    }
+
+    #[test]
+    fn fit_respects_total_characters() {
+        let embeds = vec![embed(json!({ "description": "d".repeat(4000) })); 2];
+        let (kept, overflow) = fit(embeds);
+
+        assert_eq!(kept.len(), 1);
+        assert_eq!(
+            overflow.expect("The second embed overflows.").filename,
+            OVERFLOW_FILE_NAME
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use serenity::all::{CreateAttachment, Embed};
+
+use super::embed::{self, shorten, OVERFLOW_FILE_NAME};
+
+pub const MAX_CONTENT_LENGTH: usize = 2000;
+
+const FENCE: &str = "```";
+const BLOCK_START: &str = "```\n";
+const BLOCK_END: &str = "\n```";
+const COMPACT_SEPARATOR: &str = " · ";
+
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize, poise::ChoiceParameter,
+)]
+pub enum Format {
+    #[default]
+    #[name = "Rich Embed"]
+    #[serde(rename = "e")]
+    Embed,
+    #[name = "Compact Line"]
+    #[serde(rename = "c")]
+    Compact,
+    #[name = "Code Block"]
+    #[serde(rename = "b")]
+    Block,
+}
+
+fn escape_fences(text: &str) -> String {
+    text.replace("```", "`\u{200b}`\u{200b}`")
+}
+
+fn compact(embed: &Embed) -> String {
+    let mut parts = Vec::new();
+
+    if let Some(author) = &embed.author {
+        parts.push(author.name.clone());
+    }
+
+    if let Some(title) = &embed.title {
+        parts.push(format!("**{title}**"));
+    }
+
+    if let Some(description) = &embed.description {
+        parts.push(description.clone());
+    }
+
+    for field in &embed.fields {
+        parts.push(format!("{}: {}", field.name, field.value));
+    }
+
+    // Fenced sections, like diffs, keep their line breaks
+    parts
+        .join(COMPACT_SEPARATOR)
+        .split(FENCE)
+        .enumerate()
+        .map(|(index, section)| {
+            if index % 2 == 0 {
+                collapse_whitespace(section)
+            } else {
+                section.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(FENCE)
+        .trim()
+        .to_string()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut previous_whitespace = false;
+
+    for character in text.chars() {
+        if character.is_whitespace() {
+            if !previous_whitespace {
+                collapsed.push(' ');
+            }
+        } else {
+            collapsed.push(character);
+        }
+
+        previous_whitespace = character.is_whitespace();
+    }
+
+    collapsed
+}
+
+fn block(text: &str) -> String {
+    format!("{BLOCK_START}{}{BLOCK_END}", escape_fences(text))
+}
+
+pub fn render(embeds: &[Embed], format: Format) -> (String, Option<CreateAttachment>) {
+    let content = embeds
+        .iter()
+        .map(|embed| match format {
+            Format::Compact => compact(embed),
+            Format::Embed | Format::Block => block(&embed::render(embed)),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if content.chars().count() <= MAX_CONTENT_LENGTH {
+        return (content, None);
+    }
+
+    let transcript = embeds
+        .iter()
+        .map(embed::render)
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let content = match format {
+        Format::Compact => shorten(&content, MAX_CONTENT_LENGTH),
+        Format::Embed | Format::Block => format!(
+            "{BLOCK_START}{}{BLOCK_END}",
+            shorten(
+                &escape_fences(&transcript),
+                MAX_CONTENT_LENGTH - BLOCK_START.len() - BLOCK_END.len(),
+            )
+        ),
+    };
+
+    (
+        content,
+        Some(CreateAttachment::bytes(
+            transcript.into_bytes(),
+            OVERFLOW_FILE_NAME,
+        )),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn embed(value: serde_json::Value) -> Embed {
+        serde_json::from_value(value).expect("Test embeds are valid.")
+    }
+
+    #[test]
+    fn compact_collapses_whitespace() {
+        let embed = embed(json!({
+            "title": "Message deleted",
+            "description": "first  line\n\nsecond\tline",
+        }));
+
+        assert_eq!(
+            compact(&embed),
+            "**Message deleted** · first line second line"
+        );
+    }
+
+    #[test]
+    fn compact_keeps_fenced_sections() {
+        let embed = embed(json!({
+            "description": "Changes:\n```diff\n- old  line\n+ new  line\n```\nafter  it",
+        }));
+
+        assert_eq!(
+            compact(&embed),
+            "Changes: ```diff\n- old  line\n+ new  line\n``` after it"
+        );
+    }
+
+    #[test]
+    fn compact_keeps_unclosed_fence() {
+        let embed = embed(json!({ "description": "a  b ```\ncode  here" }));
+
+        assert_eq!(compact(&embed), "a b ```\ncode  here");
+    }
+
+    #[test]
+    fn render_moves_long_content_to_file() {
+        let embeds = vec![embed(
+            json!({ "description": "d".repeat(MAX_CONTENT_LENGTH + 1) }),
+        )];
+        let (content, overflow) = render(&embeds, Format::Compact);
+
+        assert_eq!(content.chars().count(), MAX_CONTENT_LENGTH);
+        assert!(overflow.is_some());
+    }
+}
@@ -0,0 +1,80 @@
+use serenity::all::{
+    GuildId, Mentionable, PermissionOverwrite, PermissionOverwriteType, Permissions,
+};
+
+pub enum OverwriteChangeKind {
+    Added,
+    Removed,
+    Updated,
+}
+
+pub struct OverwriteChange {
+    pub kind: OverwriteChangeKind,
+    pub target: PermissionOverwriteType,
+    pub allowed: Permissions,
+    pub denied: Permissions,
+    pub inherited: Permissions,
+}
+
+pub fn names(permissions: Permissions) -> String {
+    permissions.get_permission_names().join(", ")
+}
+
+pub fn mention_target(target: &PermissionOverwriteType, guild_id: GuildId) -> String {
+    match target {
+        PermissionOverwriteType::Role(id) if id.get() == guild_id.get() => {
+            String::from("@everyone")
+        }
+        PermissionOverwriteType::Role(id) => id.mention().to_string(),
+        PermissionOverwriteType::Member(id) => id.mention().to_string(),
+        _ => String::from("?"),
+    }
+}
+
+pub fn diff_overwrites(
+    old: &[PermissionOverwrite],
+    new: &[PermissionOverwrite],
+) -> Vec<OverwriteChange> {
+    let mut changes = Vec::new();
+
+    for new in new {
+        let Some(old) = old.iter().find(|old| old.kind == new.kind) else {
+            changes.push(OverwriteChange {
+                kind: OverwriteChangeKind::Added,
+                target: new.kind,
+                allowed: new.allow,
+                denied: new.deny,
+                inherited: Permissions::empty(),
+            });
+            continue;
+        };
+
+        if old.allow == new.allow && old.deny == new.deny {
+            continue;
+        }
+
+        changes.push(OverwriteChange {
+            kind: OverwriteChangeKind::Updated,
+            target: new.kind,
+            allowed: new.allow - old.allow,
+            denied: new.deny - old.deny,
+            inherited: (old.allow | old.deny) - (new.allow | new.deny),
+        });
+    }
+
+    for old in old {
+        if new.iter().any(|new| new.kind == old.kind) {
+            continue;
+        }
+
+        changes.push(OverwriteChange {
+            kind: OverwriteChangeKind::Removed,
+            target: old.kind,
+            allowed: Permissions::empty(),
+            denied: Permissions::empty(),
+            inherited: old.allow | old.deny,
+        });
+    }
+
+    changes
+}